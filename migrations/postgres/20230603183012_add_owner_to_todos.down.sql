@@ -0,0 +1,8 @@
+-- Add down migration script here
+
+DROP INDEX todos_owner_id_idx;
+
+ALTER TABLE todos
+    DROP COLUMN owner_id;
+
+DELETE FROM users WHERE email = 'unowned-todos@example.invalid';
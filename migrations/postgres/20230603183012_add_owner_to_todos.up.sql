@@ -0,0 +1,21 @@
+-- Add up migration script here
+
+-- Todos created before ownership existed are kept and handed to a placeholder
+-- user. It has no password, so it cannot log in, but admins can still manage
+-- its todos.
+ALTER TABLE todos
+    ADD COLUMN owner_id UUID REFERENCES users (id) ON DELETE CASCADE;
+
+INSERT INTO users (id, email, first_name, created_at, updated_at)
+SELECT gen_random_uuid(), 'unowned-todos@example.invalid', 'Unowned todos',
+       now() AT TIME ZONE 'UTC', now() AT TIME ZONE 'UTC'
+WHERE EXISTS (SELECT 1 FROM todos);
+
+UPDATE todos
+SET owner_id = (SELECT id FROM users WHERE email = 'unowned-todos@example.invalid')
+WHERE owner_id IS NULL;
+
+ALTER TABLE todos
+    ALTER COLUMN owner_id SET NOT NULL;
+
+CREATE INDEX todos_owner_id_idx ON todos (owner_id);
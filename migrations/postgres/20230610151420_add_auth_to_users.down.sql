@@ -0,0 +1,6 @@
+-- Add down migration script here
+
+DROP TABLE sessions;
+
+ALTER TABLE users
+    DROP COLUMN password_hash;
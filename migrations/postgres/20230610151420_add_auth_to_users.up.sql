@@ -0,0 +1,15 @@
+-- Add up migration script here
+
+-- Users created before passwords existed keep a NULL hash and cannot log in until one is set.
+ALTER TABLE users
+    ADD COLUMN password_hash TEXT;
+
+CREATE TABLE sessions
+(
+    id              UUID PRIMARY KEY UNIQUE NOT NULL,
+    user_id         UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    created_at      TIMESTAMP NOT NULL,
+    expires_at      TIMESTAMP NOT NULL
+);
+
+CREATE INDEX sessions_user_id_idx ON sessions (user_id);
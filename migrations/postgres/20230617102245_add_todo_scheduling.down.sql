@@ -0,0 +1,6 @@
+-- Add down migration script here
+
+ALTER TABLE todos
+    DROP COLUMN completed_at,
+    DROP COLUMN due_at,
+    DROP COLUMN priority;
@@ -0,0 +1,6 @@
+-- Add up migration script here
+
+ALTER TABLE todos
+    ADD COLUMN completed_at TIMESTAMP,
+    ADD COLUMN due_at TIMESTAMP,
+    ADD COLUMN priority TEXT NOT NULL DEFAULT 'medium' CHECK (priority IN ('low', 'medium', 'high'));
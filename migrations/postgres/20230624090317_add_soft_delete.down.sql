@@ -0,0 +1,18 @@
+-- Add down migration script here
+
+DROP INDEX users_deleted_at_idx;
+DROP INDEX todos_deleted_at_idx;
+
+DELETE FROM users WHERE deleted_at IS NOT NULL;
+DELETE FROM todos WHERE deleted_at IS NOT NULL;
+
+DROP INDEX users_email_key;
+
+ALTER TABLE users
+    ADD CONSTRAINT users_email_key UNIQUE (email);
+
+ALTER TABLE users
+    DROP COLUMN deleted_at;
+
+ALTER TABLE todos
+    DROP COLUMN deleted_at;
@@ -0,0 +1,16 @@
+-- Add up migration script here
+
+ALTER TABLE todos
+    ADD COLUMN deleted_at TIMESTAMP;
+
+ALTER TABLE users
+    ADD COLUMN deleted_at TIMESTAMP;
+
+-- A deleted user must not keep their email reserved.
+ALTER TABLE users
+    DROP CONSTRAINT users_email_key;
+
+CREATE UNIQUE INDEX users_email_key ON users (email) WHERE deleted_at IS NULL;
+
+CREATE INDEX todos_deleted_at_idx ON todos (deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX users_deleted_at_idx ON users (deleted_at) WHERE deleted_at IS NOT NULL;
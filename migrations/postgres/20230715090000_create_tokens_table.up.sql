@@ -0,0 +1,17 @@
+-- Add up migration script here
+
+-- Personal access tokens. Only a SHA-256 hash of each token is kept, scopes
+-- are space separated.
+CREATE TABLE tokens
+(
+    id              UUID PRIMARY KEY NOT NULL,
+    user_id         UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    name            TEXT NOT NULL,
+    token_hash      TEXT NOT NULL UNIQUE,
+    scopes          TEXT NOT NULL,
+    expires_at      TIMESTAMP,
+    last_used_at    TIMESTAMP,
+    created_at      TIMESTAMP NOT NULL
+);
+
+CREATE INDEX tokens_user_id_idx ON tokens (user_id);
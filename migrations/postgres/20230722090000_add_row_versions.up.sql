@@ -0,0 +1,7 @@
+-- Add up migration script here
+
+ALTER TABLE todos
+    ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
+
+ALTER TABLE users
+    ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
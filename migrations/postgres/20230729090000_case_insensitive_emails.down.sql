@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP INDEX users_email_key;
+
+CREATE UNIQUE INDEX users_email_key ON users (email) WHERE deleted_at IS NULL;
@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP TABLE sessions;
+DROP TABLE todos;
+DROP TABLE users;
@@ -0,0 +1,46 @@
+-- Add up migration script here
+
+-- SQLite has no UUID or TIMESTAMP types: ids are stored as hyphenated TEXT
+-- and timestamps as UTC TEXT, which sorts chronologically.
+
+CREATE TABLE users
+(
+    id              TEXT PRIMARY KEY NOT NULL,
+    email           TEXT NOT NULL,
+    first_name      TEXT NOT NULL,
+    password_hash   TEXT,
+    created_at      TEXT NOT NULL,
+    updated_at      TEXT NOT NULL,
+    deleted_at      TEXT
+);
+
+-- A deleted user must not keep their email reserved.
+CREATE UNIQUE INDEX users_email_key ON users (email) WHERE deleted_at IS NULL;
+CREATE INDEX users_deleted_at_idx ON users (deleted_at) WHERE deleted_at IS NOT NULL;
+
+CREATE TABLE todos
+(
+    id              TEXT PRIMARY KEY NOT NULL,
+    owner_id        TEXT NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    title           TEXT NOT NULL,
+    description     TEXT NOT NULL,
+    priority        TEXT NOT NULL DEFAULT 'medium' CHECK (priority IN ('low', 'medium', 'high')),
+    due_at          TEXT,
+    completed_at    TEXT,
+    created_at      TEXT NOT NULL,
+    updated_at      TEXT NOT NULL,
+    deleted_at      TEXT
+);
+
+CREATE INDEX todos_owner_id_idx ON todos (owner_id);
+CREATE INDEX todos_deleted_at_idx ON todos (deleted_at) WHERE deleted_at IS NOT NULL;
+
+CREATE TABLE sessions
+(
+    id              TEXT PRIMARY KEY NOT NULL,
+    user_id         TEXT NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    created_at      TEXT NOT NULL,
+    expires_at      TEXT NOT NULL
+);
+
+CREATE INDEX sessions_user_id_idx ON sessions (user_id);
@@ -0,0 +1,4 @@
+-- Add up migration script here
+
+ALTER TABLE users
+    ADD COLUMN role TEXT NOT NULL DEFAULT 'member' CHECK (role IN ('admin', 'member'));
@@ -0,0 +1,17 @@
+-- Add up migration script here
+
+-- Personal access tokens. Only a SHA-256 hash of each token is kept, scopes
+-- are space separated.
+CREATE TABLE tokens
+(
+    id              TEXT PRIMARY KEY NOT NULL,
+    user_id         TEXT NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    name            TEXT NOT NULL,
+    token_hash      TEXT NOT NULL UNIQUE,
+    scopes          TEXT NOT NULL,
+    expires_at      TEXT,
+    last_used_at    TEXT,
+    created_at      TEXT NOT NULL
+);
+
+CREATE INDEX tokens_user_id_idx ON tokens (user_id);
@@ -0,0 +1,7 @@
+-- Add down migration script here
+
+ALTER TABLE users
+    DROP COLUMN version;
+
+ALTER TABLE todos
+    DROP COLUMN version;
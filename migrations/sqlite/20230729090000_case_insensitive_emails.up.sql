@@ -0,0 +1,6 @@
+-- Add up migration script here
+
+-- Emails differing only in case belong to the same person.
+DROP INDEX users_email_key;
+
+CREATE UNIQUE INDEX users_email_key ON users (LOWER(email)) WHERE deleted_at IS NULL;
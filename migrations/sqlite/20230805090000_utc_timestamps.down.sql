@@ -0,0 +1,83 @@
+-- Add down migration script here
+
+-- Rebuilds the tables without the timestamp defaults.
+
+CREATE TABLE users_new
+(
+    id              TEXT PRIMARY KEY NOT NULL,
+    email           TEXT NOT NULL,
+    first_name      TEXT NOT NULL,
+    password_hash   TEXT,
+    role            TEXT NOT NULL DEFAULT 'member' CHECK (role IN ('admin', 'member')),
+    version         INTEGER NOT NULL DEFAULT 1,
+    created_at      TEXT NOT NULL,
+    updated_at      TEXT NOT NULL,
+    deleted_at      TEXT
+);
+
+INSERT INTO users_new (id, email, first_name, password_hash, role, version, created_at, updated_at, deleted_at)
+SELECT id, email, first_name, password_hash, role, version, created_at, updated_at, deleted_at FROM users;
+
+CREATE TABLE todos_new
+(
+    id              TEXT PRIMARY KEY NOT NULL,
+    owner_id        TEXT NOT NULL REFERENCES users_new (id) ON DELETE CASCADE,
+    title           TEXT NOT NULL,
+    description     TEXT NOT NULL,
+    priority        TEXT NOT NULL DEFAULT 'medium' CHECK (priority IN ('low', 'medium', 'high')),
+    due_at          TEXT,
+    completed_at    TEXT,
+    version         INTEGER NOT NULL DEFAULT 1,
+    created_at      TEXT NOT NULL,
+    updated_at      TEXT NOT NULL,
+    deleted_at      TEXT
+);
+
+INSERT INTO todos_new (id, owner_id, title, description, priority, due_at, completed_at, version, created_at, updated_at, deleted_at)
+SELECT id, owner_id, title, description, priority, due_at, completed_at, version, created_at, updated_at, deleted_at FROM todos;
+
+CREATE TABLE sessions_new
+(
+    id              TEXT PRIMARY KEY NOT NULL,
+    user_id         TEXT NOT NULL REFERENCES users_new (id) ON DELETE CASCADE,
+    created_at      TEXT NOT NULL,
+    expires_at      TEXT NOT NULL
+);
+
+INSERT INTO sessions_new (id, user_id, created_at, expires_at)
+SELECT id, user_id, created_at, expires_at FROM sessions;
+
+CREATE TABLE tokens_new
+(
+    id              TEXT PRIMARY KEY NOT NULL,
+    user_id         TEXT NOT NULL REFERENCES users_new (id) ON DELETE CASCADE,
+    name            TEXT NOT NULL,
+    token_hash      TEXT NOT NULL UNIQUE,
+    scopes          TEXT NOT NULL,
+    expires_at      TEXT,
+    last_used_at    TEXT,
+    created_at      TEXT NOT NULL
+);
+
+INSERT INTO tokens_new (id, user_id, name, token_hash, scopes, expires_at, last_used_at, created_at)
+SELECT id, user_id, name, token_hash, scopes, expires_at, last_used_at, created_at FROM tokens;
+
+-- Children first, dropping users must not cascade to the copied rows.
+DROP TABLE tokens;
+DROP TABLE sessions;
+DROP TABLE todos;
+DROP TABLE users;
+
+-- Renaming users_new also updates the references to it.
+ALTER TABLE users_new RENAME TO users;
+ALTER TABLE todos_new RENAME TO todos;
+ALTER TABLE sessions_new RENAME TO sessions;
+ALTER TABLE tokens_new RENAME TO tokens;
+
+-- A deleted user must not keep their email reserved.
+CREATE UNIQUE INDEX users_email_key ON users (LOWER(email)) WHERE deleted_at IS NULL;
+CREATE INDEX users_deleted_at_idx ON users (deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX todos_owner_id_idx ON todos (owner_id);
+CREATE INDEX todos_deleted_at_idx ON todos (deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX sessions_user_id_idx ON sessions (user_id);
+CREATE INDEX tokens_user_id_idx ON tokens (user_id);
@@ -0,0 +1,168 @@
+use std::{future::Future, pin::Pin};
+
+use axum::{
+    async_trait,
+    body::BoxBody,
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderMap, HeaderValue, Method, Request, Response},
+    response::IntoResponse,
+};
+use tower_cookies::Cookies;
+use tower_http::auth::AsyncAuthorizeRequest;
+
+use crate::{
+    app_state::AppState,
+    domain::{
+        entities::{token::Scope, user::User},
+        services::policy::Actor,
+    },
+};
+
+use super::error::ClientApiError;
+
+pub const SESSION_COOKIE: &str = "session_id";
+
+/// Resolves the [`User`] behind the request, from a bearer token checked by
+/// [`BearerAuth`] or else from the session cookie. Add it to a handler's
+/// arguments to require an authenticated caller; requests without valid
+/// credentials are rejected with a 401.
+pub struct CurrentUser(pub User);
+
+impl CurrentUser {
+    /// The caller, for the services' permission checks.
+    pub fn actor(&self) -> Actor {
+        Actor::from(&self.0)
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for CurrentUser {
+    type Rejection = ClientApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        if let Some(current_user) = parts.extensions.remove::<CurrentUser>() {
+            return Ok(current_user);
+        }
+
+        Ok(CurrentUser(session_user(parts, state).await?))
+    }
+}
+
+/// Like [`CurrentUser`], but only from the session cookie. Guards what an
+/// access token must not reach whatever its scopes, such as minting more
+/// tokens; requests sent with a valid bearer token are rejected with a 403.
+pub struct SessionUser(pub User);
+
+impl SessionUser {
+    /// The caller, for the services' permission checks.
+    pub fn actor(&self) -> Actor {
+        Actor::from(&self.0)
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for SessionUser {
+    type Rejection = ClientApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        if parts.extensions.get::<CurrentUser>().is_some() {
+            tracing::warn!("Access tokens cannot be used here");
+            return Err(ClientApiError::InsufficientScope);
+        }
+
+        Ok(SessionUser(session_user(parts, state).await?))
+    }
+}
+
+async fn session_user(parts: &mut Parts, state: &AppState) -> Result<User, ClientApiError> {
+    let cookies = Cookies::from_request_parts(parts, state)
+        .await
+        .map_err(|(_, message)| {
+            tracing::error!("{message}");
+            ClientApiError::Unknown
+        })?;
+
+    let session_id = cookies
+        .get(SESSION_COOKIE)
+        .map(|cookie| cookie.value().to_string())
+        .ok_or(ClientApiError::Unauthorized)?;
+
+    Ok(state.auth_service.authenticate(session_id).await?)
+}
+
+type AuthorizeFuture<B> =
+    Pin<Box<dyn Future<Output = Result<Request<B>, Response<BoxBody>>> + Send>>;
+
+/// Authenticates requests sent with `Authorization: Bearer <token>` using a
+/// personal access token, for clients such as scripts that can't keep a
+/// session. `GET` and `HEAD` need the `read` scope, other methods `write`.
+///
+/// Requests without a bearer token are let through for [`CurrentUser`] to
+/// fall back on the session cookie.
+#[derive(Clone)]
+pub(super) struct BearerAuth {
+    app_state: AppState,
+}
+
+impl BearerAuth {
+    pub(super) fn new(app_state: AppState) -> Self {
+        Self { app_state }
+    }
+}
+
+impl<B> AsyncAuthorizeRequest<B> for BearerAuth
+where
+    B: Send + 'static,
+{
+    type RequestBody = B;
+    type ResponseBody = BoxBody;
+    type Future = AuthorizeFuture<B>;
+
+    fn authorize(&mut self, mut request: Request<B>) -> Self::Future {
+        let token_service = self.app_state.token_service.clone();
+
+        Box::pin(async move {
+            let Some(secret) = bearer_token(request.headers()) else {
+                return Ok(request);
+            };
+
+            let (token, user) = token_service.authenticate(secret).await.map_err(|e| {
+                let mut response = ClientApiError::from(e).into_response();
+                response
+                    .headers_mut()
+                    .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+                response
+            })?;
+
+            let scope = match *request.method() {
+                Method::GET | Method::HEAD => Scope::Read,
+                _ => Scope::Write,
+            };
+            if !token.allows(scope) {
+                tracing::warn!("Token lacks the {} scope", scope.as_str());
+                return Err(ClientApiError::InsufficientScope.into_response());
+            }
+
+            request.extensions_mut().insert(CurrentUser(user));
+
+            Ok(request)
+        })
+    }
+}
+
+/// The token of an `Authorization: Bearer` header. Other schemes are left
+/// alone.
+fn bearer_token(headers: &HeaderMap) -> Option<String> {
+    let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+
+    scheme
+        .eq_ignore_ascii_case("bearer")
+        .then(|| token.trim().to_string())
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use axum::http::{header, HeaderName, HeaderValue, Method};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use super::error::REQUEST_ID_HEADER;
+
+/// Origins allowed to call the API from a browser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CorsOrigins {
+    /// `*`. Cannot be combined with credentials.
+    Any,
+    List(Vec<HeaderValue>),
+}
+
+/// Cross-origin policy for browser clients such as an SPA served from
+/// another origin. Preflight requests are answered without reaching the
+/// routes, so they need no session.
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    pub allowed_origins: CorsOrigins,
+    pub allowed_methods: Vec<Method>,
+    pub allowed_headers: Vec<HeaderName>,
+    /// Lets browsers send the session cookie along.
+    pub allow_credentials: bool,
+    /// How long browsers may cache a preflight response.
+    pub max_age: Duration,
+}
+
+impl Default for CorsConfig {
+    /// No origin allowed, i.e. cross-origin requests are refused.
+    fn default() -> Self {
+        Self {
+            allowed_origins: CorsOrigins::List(Vec::new()),
+            allowed_methods: vec![Method::GET, Method::POST, Method::PATCH, Method::DELETE],
+            allowed_headers: vec![
+                header::CONTENT_TYPE,
+                header::AUTHORIZATION,
+                header::IF_MATCH,
+                header::IF_NONE_MATCH,
+            ],
+            allow_credentials: false,
+            max_age: Duration::from_secs(600),
+        }
+    }
+}
+
+/// The layer enforcing `cors`, `None` when no origin is allowed.
+pub fn cors_layer(cors: &CorsConfig) -> Option<CorsLayer> {
+    let allow_origin = match &cors.allowed_origins {
+        CorsOrigins::Any => AllowOrigin::any(),
+        CorsOrigins::List(origins) if origins.is_empty() => return None,
+        CorsOrigins::List(origins) => AllowOrigin::list(origins.clone()),
+    };
+
+    Some(
+        CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods(cors.allowed_methods.clone())
+            .allow_headers(cors.allowed_headers.clone())
+            .allow_credentials(cors.allow_credentials)
+            .expose_headers([HeaderName::from_static(REQUEST_ID_HEADER), header::ETAG])
+            .max_age(cors.max_age),
+    )
+}
@@ -1,35 +1,209 @@
-use axum::{http::StatusCode, response::IntoResponse};
+use axum::{
+    body::{boxed, Full},
+    http::{header, HeaderValue, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
 use serde::Serialize;
+use utoipa::ToSchema;
 
-use crate::domain::services::error::ServiceError;
+use crate::domain::services::error::{FieldError, ServiceError};
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+pub(super) const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
 
-#[derive(Serialize)]
 pub enum ClientApiError {
     NotFound,
-    BadInput,
+    BadInput(String),
+    Validation(Vec<FieldError>),
+    EmailTaken,
+    Conflict(String),
+    PreconditionFailed,
+    Unauthorized,
+    Forbidden,
+    /// The caller's access token lacks the scope the request needs.
+    InsufficientScope,
+    Unavailable,
     Unknown,
 }
 
 pub type ApiResult<T> = axum::response::Result<T, ClientApiError>;
 
+impl From<FieldError> for ClientApiError {
+    fn from(value: FieldError) -> Self {
+        ClientApiError::Validation(vec![value])
+    }
+}
+
 impl From<ServiceError> for ClientApiError {
     fn from(value: ServiceError) -> Self {
         match value {
             ServiceError::NotFound => ClientApiError::NotFound,
+            ServiceError::BadInput(message) => ClientApiError::BadInput(message),
+            ServiceError::Validation(errors) => ClientApiError::Validation(errors),
+            ServiceError::EmailTaken => ClientApiError::EmailTaken,
+            ServiceError::Conflict(message) => ClientApiError::Conflict(message),
+            ServiceError::PreconditionFailed => ClientApiError::PreconditionFailed,
+            ServiceError::Unauthorized => ClientApiError::Unauthorized,
+            ServiceError::Forbidden => ClientApiError::Forbidden,
+            ServiceError::Unavailable => ClientApiError::Unavailable,
             ServiceError::Unknown => ClientApiError::Unknown,
-            ServiceError::BadInput => ClientApiError::BadInput,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, ToSchema)]
+pub(super) struct ApiFieldError {
+    field: String,
+    message: String,
+}
+
+/// RFC 7807 problem details. `code` is stable and meant for machines, `detail`
+/// is meant for humans and may change.
+#[derive(Serialize, Clone, ToSchema)]
+pub(super) struct Problem {
+    #[serde(rename = "type")]
+    #[schema(example = "about:blank")]
+    problem_type: &'static str,
+    #[schema(example = "Not Found")]
+    title: &'static str,
+    #[schema(example = 404)]
+    status: u16,
+    #[schema(example = "not_found")]
+    code: &'static str,
+    detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<ApiFieldError>,
+}
+
+impl Problem {
+    fn new(status: StatusCode, code: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            problem_type: "about:blank",
+            title: status.canonical_reason().unwrap_or_default(),
+            status: status.as_u16(),
+            code,
+            detail: detail.into(),
+            request_id: None,
+            errors: Vec::new(),
+        }
+    }
+
+    fn body(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap_or_default()
+    }
+}
+
+impl From<ClientApiError> for Problem {
+    fn from(value: ClientApiError) -> Self {
+        match value {
+            ClientApiError::NotFound => Problem::new(
+                StatusCode::NOT_FOUND,
+                "not_found",
+                "The requested resource does not exist",
+            ),
+            ClientApiError::BadInput(message) => {
+                Problem::new(StatusCode::BAD_REQUEST, "bad_request", message)
+            }
+            ClientApiError::Validation(errors) => Problem {
+                errors: errors
+                    .into_iter()
+                    .map(|error| ApiFieldError {
+                        field: error.field,
+                        message: error.message,
+                    })
+                    .collect(),
+                ..Problem::new(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "validation_failed",
+                    "One or more fields are invalid",
+                )
+            },
+            ClientApiError::EmailTaken => Problem::new(
+                StatusCode::CONFLICT,
+                "email_taken",
+                "The email is already in use by another user",
+            ),
+            ClientApiError::Conflict(message) => {
+                Problem::new(StatusCode::CONFLICT, "conflict", message)
+            }
+            ClientApiError::PreconditionFailed => Problem::new(
+                StatusCode::PRECONDITION_FAILED,
+                "precondition_failed",
+                "The resource was modified since it was fetched, fetch it again",
+            ),
+            ClientApiError::Unauthorized => Problem::new(
+                StatusCode::UNAUTHORIZED,
+                "unauthorized",
+                "Authentication is required",
+            ),
+            ClientApiError::Forbidden => Problem::new(
+                StatusCode::FORBIDDEN,
+                "forbidden",
+                "You are not allowed to do this",
+            ),
+            ClientApiError::InsufficientScope => Problem::new(
+                StatusCode::FORBIDDEN,
+                "insufficient_scope",
+                "The access token's scopes do not allow this request",
+            ),
+            ClientApiError::Unavailable => Problem::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "service_unavailable",
+                "The service is temporarily unavailable, try again later",
+            ),
+            ClientApiError::Unknown => Problem::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal_error",
+                "An unexpected error occurred",
+            ),
         }
     }
 }
 
 impl IntoResponse for ClientApiError {
     fn into_response(self) -> axum::response::Response {
-        let response_code = match self {
-            ClientApiError::NotFound => StatusCode::NOT_FOUND,
-            ClientApiError::Unknown => StatusCode::SERVICE_UNAVAILABLE,
-            ClientApiError::BadInput => StatusCode::BAD_REQUEST,
-        };
+        let problem = Problem::from(self);
+        let status = StatusCode::from_u16(problem.status).unwrap_or_default();
+
+        let mut response = (
+            status,
+            [(header::CONTENT_TYPE, PROBLEM_CONTENT_TYPE)],
+            problem.body(),
+        )
+            .into_response();
+        // Picked up by `attach_request_id` once the request id is known
+        response.extensions_mut().insert(problem);
 
-        response_code.into_response()
+        response
+    }
+}
+
+/// Copies the request id into problem responses so clients can quote it
+/// when reporting an error.
+pub async fn attach_request_id<B>(request: Request<B>, next: Next<B>) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_owned);
+
+    let response = next.run(request).await;
+
+    let (mut parts, body) = response.into_parts();
+    match (request_id, parts.extensions.remove::<Problem>()) {
+        (Some(request_id), Some(mut problem)) => {
+            problem.request_id = Some(request_id);
+            let body = problem.body();
+            parts
+                .headers
+                .insert(header::CONTENT_LENGTH, HeaderValue::from(body.len()));
+
+            Response::from_parts(parts, boxed(Full::from(body)))
+        }
+        _ => Response::from_parts(parts, body),
     }
 }
@@ -0,0 +1,108 @@
+//! Entity tags derived from the row version, for conditional requests.
+//! Clients echo the `ETag` of a `GET` back in `If-Match` so a `PATCH` fails
+//! with a 412 instead of overwriting changes they have not seen, and in
+//! `If-None-Match` to get a 304 while their copy is still current.
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+
+use super::error::ClientApiError;
+
+fn entity_tag(version: i64) -> String {
+    format!("\"{version}\"")
+}
+
+/// `body` with the `ETag` of `version`.
+pub(super) fn with_etag(version: i64, body: impl IntoResponse) -> Response {
+    let mut response = body.into_response();
+    if let Ok(value) = HeaderValue::from_str(&entity_tag(version)) {
+        response.headers_mut().insert(header::ETAG, value);
+    }
+
+    response
+}
+
+/// The version required by `If-Match`, `None` when the header is absent or
+/// `*`. Only a single strong tag is supported since clients echo the one
+/// they were given; weak or foreign tags can never match and are rejected
+/// with a 412.
+pub(super) struct IfMatch(pub Option<i64>);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for IfMatch {
+    type Rejection = ClientApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Some(value) = parts.headers.get(header::IF_MATCH) else {
+            return Ok(Self(None));
+        };
+        let value = value
+            .to_str()
+            .map_err(|_| ClientApiError::BadInput("Malformed If-Match header".into()))?
+            .trim();
+
+        if value == "*" {
+            return Ok(Self(None));
+        }
+        if value.contains(',') {
+            return Err(ClientApiError::BadInput(
+                "If-Match must carry a single entity tag".into(),
+            ));
+        }
+
+        value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .and_then(|version| version.parse().ok())
+            .map(|version| Self(Some(version)))
+            .ok_or(ClientApiError::PreconditionFailed)
+    }
+}
+
+/// The raw `If-None-Match` header, if any.
+pub(super) struct IfNoneMatch(Option<String>);
+
+impl IfNoneMatch {
+    /// Whether the client's copy is still current, using the weak comparison
+    /// RFC 9110 prescribes for `If-None-Match`.
+    fn matches(&self, version: i64) -> bool {
+        let Some(value) = &self.0 else {
+            return false;
+        };
+        let current = entity_tag(version);
+
+        value
+            .split(',')
+            .map(str::trim)
+            .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == current)
+    }
+
+    /// A 304 when the client's copy at `version` is current, else `body`,
+    /// both carrying the `ETag`.
+    pub(super) fn respond(&self, version: i64, body: impl IntoResponse) -> Response {
+        if self.matches(version) {
+            return with_etag(version, StatusCode::NOT_MODIFIED);
+        }
+
+        with_etag(version, body)
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for IfNoneMatch {
+    type Rejection = ClientApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let value = parts
+            .headers
+            .get(header::IF_NONE_MATCH)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned);
+
+        Ok(Self(value))
+    }
+}
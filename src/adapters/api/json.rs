@@ -0,0 +1,42 @@
+//! JSON request bodies. Unlike axum's `Json`, a body that is malformed, of the
+//! wrong type or sent without `Content-Type: application/json` is answered
+//! with a problem response naming the `body` field.
+
+use axum::{async_trait, body::HttpBody, extract::FromRequest, http::Request, BoxError, Json};
+use serde::de::DeserializeOwned;
+
+use crate::domain::services::error::FieldError;
+
+use super::error::ClientApiError;
+
+pub(super) struct ApiJson<T>(pub T);
+
+#[async_trait]
+impl<T, S, B> FromRequest<S, B> for ApiJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Rejection = ClientApiError;
+
+    async fn from_request(request: Request<B>, state: &S) -> Result<Self, Self::Rejection> {
+        match Json::<T>::from_request(request, state).await {
+            Ok(Json(value)) => Ok(Self(value)),
+            // Failing to buffer the body is not the client's fault
+            Err(e) if e.status().is_server_error() => {
+                tracing::error!("{e}");
+                Err(ClientApiError::Unknown)
+            }
+            Err(e) => {
+                tracing::warn!("{e}");
+                Err(ClientApiError::Validation(vec![FieldError::new(
+                    "body",
+                    e.body_text(),
+                )]))
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, State},
+    http::{header, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+
+use crate::{app_state::AppState, infrastructure::metrics::Metrics};
+
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+pub fn routes(app_state: AppState) -> Router {
+    Router::new()
+        .route("/metrics", get(handler_metrics))
+        .with_state(app_state)
+}
+
+async fn handler_metrics(State(AppState { metrics, .. }): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)],
+        metrics.render(),
+    )
+}
+
+/// Records count, status class and latency of every request under its route
+/// template. Requests no route matched share the `unmatched` label.
+pub async fn track_metrics<B>(
+    State(metrics): State<Metrics>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let started_at = Instant::now();
+    let method = request.method().clone();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_owned())
+        .unwrap_or_else(|| "unmatched".to_owned());
+
+    let response = next.run(request).await;
+
+    metrics.record_http_request(
+        method.as_str(),
+        &route,
+        response.status().as_u16(),
+        started_at.elapsed(),
+    );
+
+    response
+}
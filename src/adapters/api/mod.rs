@@ -1,15 +1,79 @@
-use axum::Router;
+use axum::{http::HeaderName, middleware, Router};
+use tower_cookies::CookieManagerLayer;
+use tower_http::{
+    auth::AsyncRequireAuthorizationLayer,
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::TraceLayer,
+};
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
 
 use crate::{app_state::AppState, error::ServiceStartupError};
 
+use self::{
+    auth::BearerAuth,
+    error::{attach_request_id, ClientApiError, REQUEST_ID_HEADER},
+    metrics::track_metrics,
+    openapi::ApiDoc,
+};
+
+mod auth;
+pub mod cors;
 pub mod error;
+mod etag;
+mod json;
+mod metrics;
+mod openapi;
+mod pagination;
+mod path;
+mod query;
+mod routes_auth;
+mod routes_health;
 mod routes_hello;
 mod routes_todo;
+mod routes_token;
 mod routes_user;
+mod trace;
 
 pub fn build_route(app_state: AppState) -> Result<Router, ServiceStartupError> {
+    let request_id_header = HeaderName::from_static(REQUEST_ID_HEADER);
+    let metrics = app_state.metrics.clone();
+
     Ok(Router::new()
         .merge(routes_hello::routes())
+        .merge(routes_health::routes(app_state.clone()))
+        .merge(routes_auth::routes(app_state.clone()))
         .merge(routes_todo::routes(app_state.clone()))
-        .merge(routes_user::routes(app_state)))
+        .merge(routes_token::routes(app_state.clone()))
+        .merge(routes_user::routes(app_state.clone()))
+        .merge(SwaggerUi::new("/docs").url("/openapi.json", ApiDoc::openapi()))
+        .fallback(handler_not_found)
+        .layer(CookieManagerLayer::new())
+        .layer(AsyncRequireAuthorizationLayer::new(BearerAuth::new(
+            app_state,
+        )))
+        .layer(middleware::from_fn(attach_request_id))
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(trace::make_span)
+                .on_response(trace::on_response),
+        )
+        .layer(PropagateRequestIdLayer::new(request_id_header.clone()))
+        .layer(SetRequestIdLayer::new(request_id_header, MakeRequestUuid))
+        .layer(middleware::from_fn_with_state(metrics, track_metrics)))
+}
+
+/// `GET /metrics` in the Prometheus text format. Kept apart from
+/// [`build_route`] so it can be served on an admin port.
+pub fn build_metrics_route(app_state: AppState) -> Router {
+    metrics::routes(app_state)
+}
+
+/// The OpenAPI document served at `/openapi.json`, pretty printed.
+pub fn openapi_json() -> String {
+    ApiDoc::openapi().to_pretty_json().unwrap_or_default()
+}
+
+async fn handler_not_found() -> ClientApiError {
+    ClientApiError::NotFound
 }
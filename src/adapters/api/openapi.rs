@@ -0,0 +1,100 @@
+use utoipa::{
+    openapi::security::{ApiKey, ApiKeyValue, Http, HttpAuthScheme, SecurityScheme},
+    Modify, OpenApi,
+};
+
+use super::{
+    auth::SESSION_COOKIE,
+    error::{ApiFieldError, Problem},
+    pagination::{ApiSortDirection, ApiTodoPage},
+    routes_auth, routes_health, routes_hello, routes_todo, routes_token, routes_user,
+};
+
+/// The OpenAPI document, generated from the handlers' `#[utoipa::path]`
+/// attributes and the DTOs' `ToSchema` derives. New handlers and DTOs must be
+/// listed here to show up.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        routes_health::handler_healthz,
+        routes_health::handler_readyz,
+        routes_health::handler_status,
+        routes_hello::handler_hello,
+        routes_hello::handler_hello_2,
+        routes_auth::handler_login,
+        routes_auth::handler_logout,
+        routes_todo::handler_create,
+        routes_todo::handler_list,
+        routes_todo::handler_list_trash,
+        routes_todo::handler_get,
+        routes_todo::handler_update,
+        routes_todo::handler_delete,
+        routes_todo::handler_complete,
+        routes_todo::handler_reopen,
+        routes_todo::handler_restore,
+        routes_todo::handler_create_for_user,
+        routes_todo::handler_list_for_user,
+        routes_todo::handler_get_for_user,
+        routes_token::handler_create,
+        routes_token::handler_list,
+        routes_token::handler_delete,
+        routes_user::handler_create,
+        routes_user::handler_list,
+        routes_user::handler_get,
+        routes_user::handler_update,
+        routes_user::handler_delete,
+    ),
+    components(schemas(
+        Problem,
+        ApiFieldError,
+        ApiSortDirection,
+        ApiTodoPage,
+        routes_auth::LoginPayload,
+        routes_health::ApiProbe,
+        routes_health::ApiStatus,
+        routes_health::ApiStorageStatus,
+        routes_todo::ApiTodo,
+        routes_todo::ApiPriority,
+        routes_todo::ApiTodoSortField,
+        routes_todo::CreatePayload,
+        routes_todo::UpdatePayload,
+        routes_token::ApiScope,
+        routes_token::ApiToken,
+        routes_token::ApiCreatedToken,
+        routes_token::CreatePayload,
+        routes_user::ApiUser,
+        routes_user::ApiRole,
+        routes_user::CreatePayload,
+        routes_user::UpdatePayload,
+    )),
+    modifiers(&SecuritySchemes),
+    tags(
+        (name = "auth", description = "Session login and logout"),
+        (name = "todo", description = "Todos of the logged in user"),
+        (name = "user", description = "User registration and profiles"),
+        (name = "token", description = "Personal access tokens for scripts and CI"),
+        (name = "health", description = "Probes and status for operators"),
+        (name = "hello", description = "Greetings"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Registers the security schemes referenced by authenticated routes: the
+/// `session` cookie set by `POST /auth/login` and `bearer` personal access
+/// tokens.
+struct SecuritySchemes;
+
+impl Modify for SecuritySchemes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "session",
+                SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::new(SESSION_COOKIE))),
+            );
+            components.add_security_scheme(
+                "bearer",
+                SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)),
+            );
+        }
+    }
+}
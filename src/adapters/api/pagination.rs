@@ -0,0 +1,126 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use utoipa::ToSchema;
+
+use crate::domain::{
+    repositories::pagination::{Cursor, CursorValue, Page, SortDirection},
+    services::error::FieldError,
+};
+
+use super::{error::ClientApiError, routes_todo::ApiTodo};
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum ApiSortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl From<ApiSortDirection> for SortDirection {
+    fn from(value: ApiSortDirection) -> Self {
+        match value {
+            ApiSortDirection::Asc => SortDirection::Asc,
+            ApiSortDirection::Desc => SortDirection::Desc,
+        }
+    }
+}
+
+impl From<SortDirection> for ApiSortDirection {
+    fn from(value: SortDirection) -> Self {
+        match value {
+            SortDirection::Asc => ApiSortDirection::Asc,
+            SortDirection::Desc => ApiSortDirection::Desc,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+#[aliases(ApiTodoPage = ApiPage<ApiTodo>)]
+pub(super) struct ApiPage<T> {
+    items: Vec<T>,
+    /// Pass as `cursor` to fetch the next page, `null` on the last page.
+    next_cursor: Option<String>,
+}
+
+impl<T> ApiPage<T> {
+    /// `A` is the API representation of the sort field the page was sorted by.
+    pub fn from_page<E, S, A>(page: Page<E, S>) -> Self
+    where
+        T: From<E>,
+        A: From<S> + Serialize,
+    {
+        Self {
+            items: page.items.into_iter().map(T::from).collect(),
+            next_cursor: page.next_cursor.map(encode_cursor::<S, A>),
+        }
+    }
+}
+
+/// Wire format of a cursor. It is base64 encoded so clients treat it as opaque.
+#[derive(Serialize, Deserialize)]
+struct ApiCursor<A> {
+    sort: A,
+    order: ApiSortDirection,
+    value: String,
+    id: String,
+}
+
+fn encode_cursor<S, A>(cursor: Cursor<S>) -> String
+where
+    A: From<S> + Serialize,
+{
+    let value = match cursor.value {
+        CursorValue::Timestamp(value) => value.format(&Rfc3339).unwrap_or_default(),
+        CursorValue::Text(value) => value,
+    };
+    let cursor = ApiCursor {
+        sort: A::from(cursor.sort_by),
+        order: cursor.direction.into(),
+        value,
+        id: cursor.id,
+    };
+
+    URL_SAFE_NO_PAD.encode(serde_json::to_vec(&cursor).unwrap_or_default())
+}
+
+/// `is_timestamp` tells whether the encoded sort field holds timestamps or text.
+pub(super) fn decode_cursor<S, A>(
+    cursor: &str,
+    is_timestamp: impl Fn(&A) -> bool,
+) -> Result<Cursor<S>, ClientApiError>
+where
+    A: DeserializeOwned,
+    S: From<A>,
+{
+    let bytes = URL_SAFE_NO_PAD.decode(cursor).map_err(|e| {
+        tracing::warn!("Invalid cursor: {e}");
+        invalid_cursor()
+    })?;
+    let cursor: ApiCursor<A> = serde_json::from_slice(&bytes).map_err(|e| {
+        tracing::warn!("Invalid cursor: {e}");
+        invalid_cursor()
+    })?;
+
+    let value = if is_timestamp(&cursor.sort) {
+        let value = OffsetDateTime::parse(&cursor.value, &Rfc3339).map_err(|e| {
+            tracing::warn!("Invalid cursor: {e}");
+            invalid_cursor()
+        })?;
+        CursorValue::Timestamp(value)
+    } else {
+        CursorValue::Text(cursor.value)
+    };
+
+    Ok(Cursor {
+        sort_by: cursor.sort.into(),
+        direction: cursor.order.into(),
+        value,
+        id: cursor.id,
+    })
+}
+
+fn invalid_cursor() -> ClientApiError {
+    ClientApiError::Validation(vec![FieldError::new("cursor", "is not a valid cursor")])
+}
@@ -0,0 +1,40 @@
+//! Path parameters such as typed ids. Unlike axum's `Path`, a malformed
+//! parameter is answered with a problem response, e.g. a 400 saying that
+//! `/todo/abc` does not name a valid todo id.
+
+use axum::{
+    async_trait,
+    extract::{self, rejection::PathRejection, FromRequestParts},
+    http::request::Parts,
+};
+use serde::de::DeserializeOwned;
+
+use super::error::ClientApiError;
+
+pub(super) struct Path<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Path<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = ClientApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match extract::Path::<T>::from_request_parts(parts, state).await {
+            Ok(extract::Path(value)) => Ok(Self(value)),
+            // The others are mismatches between routes and handlers
+            Err(PathRejection::FailedToDeserializePathParams(e))
+                if e.status().is_client_error() =>
+            {
+                tracing::warn!("{}", e.kind());
+                Err(ClientApiError::BadInput(e.kind().to_string()))
+            }
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ClientApiError::Unknown)
+            }
+        }
+    }
+}
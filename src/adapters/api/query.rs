@@ -0,0 +1,38 @@
+//! Query strings. Unlike axum's `Query`, parameters that fail to deserialize,
+//! e.g. `?limit=abc`, are answered with a problem response naming the `query`
+//! field.
+
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Query},
+    http::request::Parts,
+};
+use serde::de::DeserializeOwned;
+
+use crate::domain::services::error::FieldError;
+
+use super::error::ClientApiError;
+
+pub(super) struct ApiQuery<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ApiQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ClientApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match Query::<T>::from_request_parts(parts, state).await {
+            Ok(Query(value)) => Ok(Self(value)),
+            Err(e) => {
+                tracing::warn!("{e}");
+                Err(ClientApiError::Validation(vec![FieldError::new(
+                    "query",
+                    e.body_text(),
+                )]))
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, routing::post, Router};
+use serde::Deserialize;
+use tower_cookies::{cookie::SameSite, Cookie, Cookies};
+use utoipa::ToSchema;
+
+use crate::{app_state::AppState, domain::services::auth_service::LoginInput};
+
+use super::{auth::SESSION_COOKIE, error::ApiResult, json::ApiJson, routes_user::ApiUser};
+
+pub fn routes(app_state: AppState) -> Router {
+    Router::new()
+        .route("/auth/login", post(handler_login))
+        .route("/auth/logout", post(handler_logout))
+        .with_state(app_state)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub(super) struct LoginPayload {
+    email: String,
+    #[schema(format = Password)]
+    password: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    operation_id = "login",
+    tag = "auth",
+    request_body = LoginPayload,
+    responses(
+        (status = 200, description = "Logged in, the session cookie is set", body = ApiUser),
+        (status = 401, description = "Wrong email or password", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid payload", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn handler_login(
+    State(AppState {
+        auth_service,
+        clock,
+        secure_cookies,
+        ..
+    }): State<AppState>,
+    cookies: Cookies,
+    ApiJson(payload): ApiJson<LoginPayload>,
+) -> ApiResult<ApiUser> {
+    tracing::info!("Post /auth/login");
+
+    let input = LoginInput {
+        email: payload.email,
+        password: payload.password,
+    };
+
+    let (session, user) = auth_service.login(input).await?;
+
+    let max_age = session.expires_at - clock.now();
+    let cookie = Cookie::build(SESSION_COOKIE, session.id)
+        .path("/")
+        .http_only(true)
+        .secure(secure_cookies)
+        .same_site(SameSite::Lax)
+        .max_age(max_age)
+        .finish();
+    cookies.add(cookie);
+
+    Ok(user.into())
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    operation_id = "logout",
+    tag = "auth",
+    responses(
+        (status = 204, description = "Logged out, the session cookie is removed"),
+    ),
+)]
+async fn handler_logout(
+    State(AppState { auth_service, .. }): State<AppState>,
+    cookies: Cookies,
+) -> ApiResult<impl IntoResponse> {
+    tracing::info!("Post /auth/logout");
+
+    if let Some(cookie) = cookies.get(SESSION_COOKIE) {
+        auth_service.logout(cookie.value().to_string()).await?;
+    }
+    cookies.remove(Cookie::build(SESSION_COOKIE, "").path("/").finish());
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,112 @@
+use axum::{extract::State, routing::get, Json, Router};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::{
+    app_state::AppState,
+    domain::{repositories::health_repository::StorageHealth, services::health_service::Status},
+};
+
+use super::error::ApiResult;
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct ApiProbe {
+    #[schema(example = "ok")]
+    status: &'static str,
+}
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct ApiStorageStatus {
+    /// `postgres`, `sqlite` or `memory`
+    backend: &'static str,
+    pool_size: u32,
+    pool_idle: usize,
+    /// Latest applied migration, `null` for in-memory storage
+    migration_version: Option<i64>,
+}
+
+impl From<StorageHealth> for ApiStorageStatus {
+    fn from(value: StorageHealth) -> Self {
+        Self {
+            backend: value.backend,
+            pool_size: value.pool_size,
+            pool_idle: value.pool_idle,
+            migration_version: value.migration_version,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct ApiStatus {
+    version: &'static str,
+    uptime_seconds: u64,
+    storage: ApiStorageStatus,
+}
+
+impl From<Status> for ApiStatus {
+    fn from(value: Status) -> Self {
+        Self {
+            version: value.version,
+            uptime_seconds: value.uptime.as_secs(),
+            storage: value.storage.into(),
+        }
+    }
+}
+
+pub fn routes(app_state: AppState) -> Router {
+    Router::new()
+        .route("/healthz", get(handler_healthz))
+        .route("/readyz", get(handler_readyz))
+        .route("/status", get(handler_status))
+        .with_state(app_state)
+}
+
+// Liveness, no dependencies are checked
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    operation_id = "healthz",
+    tag = "health",
+    responses(
+        (status = 200, description = "The process is alive", body = ApiProbe),
+    ),
+)]
+async fn handler_healthz() -> Json<ApiProbe> {
+    Json(ApiProbe { status: "ok" })
+}
+
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    operation_id = "readyz",
+    tag = "health",
+    responses(
+        (status = 200, description = "Ready to serve requests", body = ApiProbe),
+        (status = 503, description = "The storage is unreachable or not migrated, or the server is shutting down", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn handler_readyz(
+    State(AppState { health_service, .. }): State<AppState>,
+) -> ApiResult<Json<ApiProbe>> {
+    health_service.readiness().await?;
+
+    Ok(Json(ApiProbe { status: "ready" }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/status",
+    operation_id = "status",
+    tag = "health",
+    responses(
+        (status = 200, description = "Version, uptime and storage state", body = ApiStatus),
+        (status = 503, description = "The storage is unreachable", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn handler_status(
+    State(AppState { health_service, .. }): State<AppState>,
+) -> ApiResult<Json<ApiStatus>> {
+    tracing::info!("Get /status");
+
+    Ok(Json(health_service.status().await?.into()))
+}
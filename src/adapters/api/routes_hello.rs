@@ -5,8 +5,10 @@ use axum::{
     Router,
 };
 use serde::Deserialize;
+use utoipa::IntoParams;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct HelloParams {
     name: Option<String>,
 }
@@ -18,6 +20,16 @@ pub fn routes() -> Router {
 }
 
 // e.g. `/hello?name=Darren`
+#[utoipa::path(
+    get,
+    path = "/hello",
+    operation_id = "hello",
+    tag = "hello",
+    params(HelloParams),
+    responses(
+        (status = 200, description = "A greeting", body = String, content_type = "text/plain"),
+    ),
+)]
 async fn handler_hello(Query(params): Query<HelloParams>) -> impl IntoResponse {
     tracing::info!("->> {:12} - hello handler: {:?}", "HANDLER", params);
     let name = params.name.as_deref().unwrap_or("World");
@@ -26,6 +38,16 @@ async fn handler_hello(Query(params): Query<HelloParams>) -> impl IntoResponse {
 }
 
 // e.g. `/hello/Darren`
+#[utoipa::path(
+    get,
+    path = "/hello/{name}",
+    operation_id = "hello_name",
+    tag = "hello",
+    params(("name" = String, Path, description = "Who to greet")),
+    responses(
+        (status = 200, description = "A greeting", body = String, content_type = "text/plain"),
+    ),
+)]
 async fn handler_hello_2(Path(name): Path<String>) -> impl IntoResponse {
     tracing::info!("->> {:12} - hello handler 2: {:?}", "HANDLER", name);
 
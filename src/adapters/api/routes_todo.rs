@@ -1,34 +1,133 @@
 use axum::{
-    extract::{Path, State},
-    response::IntoResponse,
-    routing::{patch, post},
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, patch, post},
     Json, Router,
 };
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use time::OffsetDateTime;
+use utoipa::{IntoParams, ToSchema};
 
 use crate::{
     app_state::AppState,
     domain::{
-        entities::todo::Todo,
-        services::todo_service::{CreateInput, UpdateInput},
+        entities::todo::{Priority, Todo},
+        ids::{TodoId, UserId},
+        repositories::todo_repository::TodoSortField,
+        services::{
+            policy::Actor,
+            todo_service::{CreateInput, ListInput, UpdateInput},
+        },
+        values::TodoTitle,
     },
 };
 
-use super::error::ApiResult;
+use super::{
+    auth::CurrentUser,
+    error::{ApiResult, ClientApiError},
+    etag::{with_etag, IfMatch, IfNoneMatch},
+    json::ApiJson,
+    pagination::{decode_cursor, ApiPage, ApiSortDirection},
+    path::Path,
+    query::ApiQuery,
+};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum ApiPriority {
+    Low,
+    Medium,
+    High,
+}
+
+impl From<Priority> for ApiPriority {
+    fn from(value: Priority) -> Self {
+        match value {
+            Priority::Low => ApiPriority::Low,
+            Priority::Medium => ApiPriority::Medium,
+            Priority::High => ApiPriority::High,
+        }
+    }
+}
+
+impl From<ApiPriority> for Priority {
+    fn from(value: ApiPriority) -> Self {
+        match value {
+            ApiPriority::Low => Priority::Low,
+            ApiPriority::Medium => Priority::Medium,
+            ApiPriority::High => Priority::High,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum ApiTodoSortField {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    Title,
+}
+
+impl From<TodoSortField> for ApiTodoSortField {
+    fn from(value: TodoSortField) -> Self {
+        match value {
+            TodoSortField::CreatedAt => ApiTodoSortField::CreatedAt,
+            TodoSortField::UpdatedAt => ApiTodoSortField::UpdatedAt,
+            TodoSortField::Title => ApiTodoSortField::Title,
+        }
+    }
+}
+
+impl From<ApiTodoSortField> for TodoSortField {
+    fn from(value: ApiTodoSortField) -> Self {
+        match value {
+            ApiTodoSortField::CreatedAt => TodoSortField::CreatedAt,
+            ApiTodoSortField::UpdatedAt => TodoSortField::UpdatedAt,
+            ApiTodoSortField::Title => TodoSortField::Title,
+        }
+    }
+}
 
-#[derive(Serialize)]
-struct ApiTodo {
+#[derive(Serialize, ToSchema)]
+pub(super) struct ApiTodo {
     id: String,
+    owner_id: String,
     title: String,
     description: String,
+    priority: ApiPriority,
+    #[serde(with = "time::serde::rfc3339::option")]
+    due_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    completed_at: Option<OffsetDateTime>,
+    /// Only present on todos in the trash.
+    #[serde(
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    deleted_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339")]
+    created_at: OffsetDateTime,
+    /// Changes on every update, e.g. to fetch todos changed since a sync with
+    /// `updated_after`.
+    #[serde(with = "time::serde::rfc3339")]
+    updated_at: OffsetDateTime,
 }
 
 impl From<Todo> for ApiTodo {
     fn from(value: Todo) -> Self {
         Self {
-            id: value.id,
+            id: value.id.to_string(),
+            owner_id: value.owner_id.to_string(),
             title: value.title,
             description: value.description,
+            priority: value.priority.into(),
+            due_at: value.due_at,
+            completed_at: value.completed_at,
+            deleted_at: value.deleted_at,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
         }
     }
 }
@@ -42,71 +141,476 @@ impl IntoResponse for ApiTodo {
 pub fn routes(app_state: AppState) -> Router {
     Router::new()
         .route("/todo", post(handler_create).get(handler_list))
-        .route("/todo/:id", patch(handler_update).get(handler_get))
+        .route("/todo/trash", get(handler_list_trash))
+        .route(
+            "/todo/:id",
+            patch(handler_update)
+                .get(handler_get)
+                .delete(handler_delete),
+        )
+        .route("/todo/:id/complete", post(handler_complete))
+        .route("/todo/:id/reopen", post(handler_reopen))
+        .route("/todo/:id/restore", post(handler_restore))
+        .route(
+            "/user/:id/todo",
+            post(handler_create_for_user).get(handler_list_for_user),
+        )
+        .route("/user/:id/todo/:todo_id", get(handler_get_for_user))
         .with_state(app_state)
 }
 
+// e.g. `/todo?completed=false&q=milk&sort=updated_at&order=desc&limit=20`
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ListParams {
+    completed: Option<bool>,
+    /// Case-insensitive search in title and description
+    q: Option<String>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    created_after: Option<OffsetDateTime>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    created_before: Option<OffsetDateTime>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    updated_after: Option<OffsetDateTime>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    updated_before: Option<OffsetDateTime>,
+    #[serde(default)]
+    #[param(inline)]
+    sort: ApiTodoSortField,
+    #[serde(default)]
+    #[param(inline)]
+    order: ApiSortDirection,
+    /// `next_cursor` of the previous page
+    cursor: Option<String>,
+    /// Page size, at most 100
+    limit: Option<u32>,
+}
+
+impl TryFrom<ListParams> for ListInput {
+    type Error = ClientApiError;
+
+    fn try_from(value: ListParams) -> Result<Self, Self::Error> {
+        let after = value
+            .cursor
+            .map(|cursor| {
+                decode_cursor::<TodoSortField, ApiTodoSortField>(&cursor, |sort| {
+                    !matches!(sort, ApiTodoSortField::Title)
+                })
+            })
+            .transpose()?;
+
+        Ok(ListInput {
+            completed: value.completed,
+            search: value.q,
+            created_after: value.created_after,
+            created_before: value.created_before,
+            updated_after: value.updated_after,
+            updated_before: value.updated_before,
+            sort_by: value.sort.into(),
+            direction: value.order.into(),
+            after,
+            limit: value.limit,
+        })
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/todo",
+    operation_id = "list_todos",
+    tag = "todo",
+    params(ListParams),
+    responses(
+        (status = 200, description = "A page of the caller's todos", body = ApiTodoPage),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid cursor", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = []), ("bearer" = [])),
+)]
 async fn handler_list(
     State(AppState { todo_service, .. }): State<AppState>,
-) -> ApiResult<Json<Vec<ApiTodo>>> {
-    tracing::info!("Get /todo");
+    CurrentUser(user): CurrentUser,
+    ApiQuery(params): ApiQuery<ListParams>,
+) -> ApiResult<Json<ApiPage<ApiTodo>>> {
+    tracing::info!("Get /todo | {params:?}");
 
-    Ok(Json(
-        todo_service
-            .list()
-            .await?
-            .into_iter()
-            .map(|entity| entity.into())
-            .collect(),
-    ))
+    let page = todo_service
+        .list_for_user(Actor::from(&user), user.id, params.try_into()?)
+        .await?;
+
+    Ok(Json(ApiPage::from_page::<_, _, ApiTodoSortField>(page)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/todo/{id}",
+    operation_id = "get_todo",
+    tag = "todo",
+    params(("id" = String, Path, description = "Todo id"),
+        ("If-None-Match" = Option<String>, Header, description = "`ETag` of the copy the client holds")),
+    responses(
+        (status = 200, description = "The todo", body = ApiTodo,
+            headers(("ETag" = String, description = "Version of the todo"))),
+        (status = 304, description = "The client's copy is current"),
+        (status = 400, description = "Malformed id", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Owned by another user and the caller is not an admin", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "The todo does not exist", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = []), ("bearer" = [])),
+)]
 async fn handler_get(
     State(AppState { todo_service, .. }): State<AppState>,
-    Path(id): Path<String>,
-) -> ApiResult<ApiTodo> {
+    current_user: CurrentUser,
+    Path(id): Path<TodoId>,
+    if_none_match: IfNoneMatch,
+) -> ApiResult<Response> {
     tracing::info!("Get /todo/{id}");
 
-    Ok(todo_service.get(id).await?.into())
+    let todo = todo_service.get(current_user.actor(), id).await?;
+
+    Ok(if_none_match.respond(todo.version, ApiTodo::from(todo)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/user/{id}/todo",
+    operation_id = "list_user_todos",
+    tag = "todo",
+    params(("id" = String, Path, description = "User id"), ListParams),
+    responses(
+        (status = 200, description = "A page of the user's todos", body = ApiTodoPage),
+        (status = 400, description = "Malformed id", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Owned by another user and the caller is not an admin", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "The user does not exist", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid cursor", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = []), ("bearer" = [])),
+)]
+async fn handler_list_for_user(
+    State(AppState { todo_service, .. }): State<AppState>,
+    current_user: CurrentUser,
+    Path(user_id): Path<UserId>,
+    ApiQuery(params): ApiQuery<ListParams>,
+) -> ApiResult<Json<ApiPage<ApiTodo>>> {
+    tracing::info!("Get /user/{user_id}/todo | {params:?}");
+
+    let page = todo_service
+        .list_for_user(current_user.actor(), user_id, params.try_into()?)
+        .await?;
+
+    Ok(Json(ApiPage::from_page::<_, _, ApiTodoSortField>(page)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/user/{id}/todo/{todo_id}",
+    operation_id = "get_user_todo",
+    tag = "todo",
+    params(("id" = String, Path, description = "User id"),
+        ("todo_id" = String, Path, description = "Todo id")),
+    responses(
+        (status = 200, description = "The todo", body = ApiTodo),
+        (status = 400, description = "Malformed id", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Owned by another user and the caller is not an admin", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "The todo does not exist", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = []), ("bearer" = [])),
+)]
+async fn handler_get_for_user(
+    State(AppState { todo_service, .. }): State<AppState>,
+    current_user: CurrentUser,
+    Path((user_id, todo_id)): Path<(UserId, TodoId)>,
+) -> ApiResult<ApiTodo> {
+    tracing::info!("Get /user/{user_id}/todo/{todo_id}");
+
+    Ok(todo_service
+        .get_for_user(current_user.actor(), user_id, todo_id)
+        .await?
+        .into())
 }
 
-#[derive(Debug, Deserialize)]
-struct CreatePayload {
+#[derive(Debug, Deserialize, ToSchema)]
+#[schema(as = CreateTodoPayload)]
+pub(super) struct CreatePayload {
     title: String,
     description: String,
+    /// Defaults to `medium`
+    priority: Option<ApiPriority>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    due_at: Option<OffsetDateTime>,
 }
 
+#[utoipa::path(
+    post,
+    path = "/todo",
+    operation_id = "create_todo",
+    tag = "todo",
+    request_body = CreateTodoPayload,
+    responses(
+        (status = 200, description = "The created todo, owned by the caller", body = ApiTodo),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid payload", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = []), ("bearer" = [])),
+)]
 async fn handler_create(
     State(AppState { todo_service, .. }): State<AppState>,
-    Json(payload): Json<CreatePayload>,
+    CurrentUser(user): CurrentUser,
+    ApiJson(payload): ApiJson<CreatePayload>,
 ) -> ApiResult<ApiTodo> {
     tracing::info!("Post /todo | {payload:?}");
 
     let input = CreateInput {
-        title: payload.title,
+        owner_id: user.id,
+        title: TodoTitle::parse(payload.title)?,
+        description: payload.description,
+        priority: payload.priority.map(Priority::from),
+        due_at: payload.due_at,
+    };
+
+    Ok(todo_service.create(Actor::from(&user), input).await?.into())
+}
+
+#[utoipa::path(
+    post,
+    path = "/user/{id}/todo",
+    operation_id = "create_user_todo",
+    tag = "todo",
+    params(("id" = String, Path, description = "User id")),
+    request_body = CreateTodoPayload,
+    responses(
+        (status = 200, description = "The created todo", body = ApiTodo),
+        (status = 400, description = "Malformed id", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Owned by another user and the caller is not an admin", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "The user does not exist", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid payload", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = []), ("bearer" = [])),
+)]
+async fn handler_create_for_user(
+    State(AppState { todo_service, .. }): State<AppState>,
+    current_user: CurrentUser,
+    Path(user_id): Path<UserId>,
+    ApiJson(payload): ApiJson<CreatePayload>,
+) -> ApiResult<ApiTodo> {
+    tracing::info!("Post /user/{user_id}/todo | {payload:?}");
+
+    let input = CreateInput {
+        owner_id: user_id,
+        title: TodoTitle::parse(payload.title)?,
         description: payload.description,
+        priority: payload.priority.map(Priority::from),
+        due_at: payload.due_at,
     };
 
-    Ok(todo_service.create(input).await?.into())
+    Ok(todo_service
+        .create(current_user.actor(), input)
+        .await?
+        .into())
 }
 
-#[derive(Debug, Deserialize)]
-struct UpdatePayload {
+#[derive(Debug, Deserialize, ToSchema)]
+#[schema(as = UpdateTodoPayload)]
+pub(super) struct UpdatePayload {
     title: Option<String>,
     description: Option<String>,
+    priority: Option<ApiPriority>,
+    /// Omitting the field keeps the due date, an explicit `null` clears it.
+    #[serde(default, deserialize_with = "deserialize_nullable_datetime")]
+    #[schema(value_type = Option<String>, format = DateTime, nullable)]
+    due_at: Option<Option<OffsetDateTime>>,
+}
+
+fn deserialize_nullable_datetime<'de, D>(
+    deserializer: D,
+) -> Result<Option<Option<OffsetDateTime>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    time::serde::rfc3339::option::deserialize(deserializer).map(Some)
 }
 
+#[utoipa::path(
+    patch,
+    path = "/todo/{id}",
+    operation_id = "update_todo",
+    tag = "todo",
+    params(("id" = String, Path, description = "Todo id"),
+        ("If-Match" = Option<String>, Header, description = "`ETag` the update is based on")),
+    request_body = UpdateTodoPayload,
+    responses(
+        (status = 200, description = "The updated todo", body = ApiTodo,
+            headers(("ETag" = String, description = "Version of the todo"))),
+        (status = 400, description = "Malformed id", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Owned by another user and the caller is not an admin", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "The todo does not exist", body = Problem, content_type = "application/problem+json"),
+        (status = 412, description = "The todo changed since the `If-Match` version", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid payload", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = []), ("bearer" = [])),
+)]
 async fn handler_update(
     State(AppState { todo_service, .. }): State<AppState>,
-    Path(id): Path<String>,
-    Json(payload): Json<UpdatePayload>,
-) -> ApiResult<ApiTodo> {
+    current_user: CurrentUser,
+    Path(id): Path<TodoId>,
+    IfMatch(version): IfMatch,
+    ApiJson(payload): ApiJson<UpdatePayload>,
+) -> ApiResult<Response> {
     tracing::info!("Patch /todo/{id} | {payload:?}");
 
     let input = UpdateInput {
-        title: payload.title,
+        title: payload.title.map(TodoTitle::parse).transpose()?,
         description: payload.description,
+        priority: payload.priority.map(Priority::from),
+        due_at: payload.due_at,
+        version,
     };
 
-    Ok(todo_service.update(id, input).await?.into())
+    let todo = todo_service.update(current_user.actor(), id, input).await?;
+
+    Ok(with_etag(todo.version, ApiTodo::from(todo)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/todo/{id}/complete",
+    operation_id = "complete_todo",
+    tag = "todo",
+    params(("id" = String, Path, description = "Todo id")),
+    responses(
+        (status = 200, description = "The completed todo, unchanged if it already was", body = ApiTodo,
+            headers(("ETag" = String, description = "Version of the todo"))),
+        (status = 400, description = "Malformed id", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Owned by another user and the caller is not an admin", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "The todo does not exist", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = []), ("bearer" = [])),
+)]
+async fn handler_complete(
+    State(AppState { todo_service, .. }): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<TodoId>,
+) -> ApiResult<Response> {
+    tracing::info!("Post /todo/{id}/complete");
+
+    let todo = todo_service.complete(current_user.actor(), id).await?;
+
+    Ok(with_etag(todo.version, ApiTodo::from(todo)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/todo/{id}/reopen",
+    operation_id = "reopen_todo",
+    tag = "todo",
+    params(("id" = String, Path, description = "Todo id")),
+    responses(
+        (status = 200, description = "The reopened todo", body = ApiTodo,
+            headers(("ETag" = String, description = "Version of the todo"))),
+        (status = 400, description = "Malformed id", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Owned by another user and the caller is not an admin", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "The todo does not exist", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = []), ("bearer" = [])),
+)]
+async fn handler_reopen(
+    State(AppState { todo_service, .. }): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<TodoId>,
+) -> ApiResult<Response> {
+    tracing::info!("Post /todo/{id}/reopen");
+
+    let todo = todo_service.reopen(current_user.actor(), id).await?;
+
+    Ok(with_etag(todo.version, ApiTodo::from(todo)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/todo/{id}",
+    operation_id = "delete_todo",
+    tag = "todo",
+    params(("id" = String, Path, description = "Todo id")),
+    responses(
+        (status = 204, description = "The todo was moved to the trash"),
+        (status = 400, description = "Malformed id", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Owned by another user and the caller is not an admin", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "The todo does not exist", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = []), ("bearer" = [])),
+)]
+async fn handler_delete(
+    State(AppState { todo_service, .. }): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<TodoId>,
+) -> ApiResult<StatusCode> {
+    tracing::info!("Delete /todo/{id}");
+
+    todo_service.delete(current_user.actor(), id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/todo/trash",
+    operation_id = "list_trash",
+    tag = "todo",
+    responses(
+        (status = 200, description = "The caller's trashed todos, most recently deleted first", body = [ApiTodo]),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = []), ("bearer" = [])),
+)]
+async fn handler_list_trash(
+    State(AppState { todo_service, .. }): State<AppState>,
+    CurrentUser(user): CurrentUser,
+) -> ApiResult<Json<Vec<ApiTodo>>> {
+    tracing::info!("Get /todo/trash");
+
+    Ok(Json(
+        todo_service
+            .list_trash_for_user(Actor::from(&user), user.id)
+            .await?
+            .into_iter()
+            .map(|entity| entity.into())
+            .collect(),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/todo/{id}/restore",
+    operation_id = "restore_todo",
+    tag = "todo",
+    params(("id" = String, Path, description = "Todo id")),
+    responses(
+        (status = 200, description = "The restored todo", body = ApiTodo,
+            headers(("ETag" = String, description = "Version of the todo"))),
+        (status = 400, description = "Malformed id", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Owned by another user and the caller is not an admin", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "The todo does not exist", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "The todo is not in the trash", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = []), ("bearer" = [])),
+)]
+async fn handler_restore(
+    State(AppState { todo_service, .. }): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<TodoId>,
+) -> ApiResult<Response> {
+    tracing::info!("Post /todo/{id}/restore");
+
+    let todo = todo_service.restore(current_user.actor(), id).await?;
+
+    Ok(with_etag(todo.version, ApiTodo::from(todo)))
 }
@@ -0,0 +1,222 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{delete, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+
+use crate::{
+    app_state::AppState,
+    domain::{
+        entities::token::{Scope, Token},
+        ids::{TokenId, UserId},
+        services::token_service::{CreateInput, CreatedToken},
+    },
+};
+
+use super::{
+    auth::{CurrentUser, SessionUser},
+    error::ApiResult,
+    json::ApiJson,
+    path::Path,
+};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum ApiScope {
+    /// `GET` and `HEAD` requests
+    Read,
+    /// Every other method
+    Write,
+}
+
+impl From<Scope> for ApiScope {
+    fn from(value: Scope) -> Self {
+        match value {
+            Scope::Read => ApiScope::Read,
+            Scope::Write => ApiScope::Write,
+        }
+    }
+}
+
+impl From<ApiScope> for Scope {
+    fn from(value: ApiScope) -> Self {
+        match value {
+            ApiScope::Read => Scope::Read,
+            ApiScope::Write => Scope::Write,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct ApiToken {
+    id: String,
+    name: String,
+    scopes: Vec<ApiScope>,
+    /// `null` for tokens that never expire
+    #[serde(with = "time::serde::rfc3339::option")]
+    expires_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    last_used_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339")]
+    created_at: OffsetDateTime,
+}
+
+impl From<Token> for ApiToken {
+    fn from(value: Token) -> Self {
+        Self {
+            id: value.id.to_string(),
+            name: value.name,
+            scopes: value.scopes.into_iter().map(ApiScope::from).collect(),
+            expires_at: value.expires_at,
+            last_used_at: value.last_used_at,
+            created_at: value.created_at,
+        }
+    }
+}
+
+/// A token as returned on creation, the only time its secret is shown.
+#[derive(Serialize, ToSchema)]
+pub(super) struct ApiCreatedToken {
+    #[serde(flatten)]
+    details: ApiToken,
+    /// Send as `Authorization: Bearer <token>`
+    token: String,
+}
+
+impl From<CreatedToken> for ApiCreatedToken {
+    fn from(value: CreatedToken) -> Self {
+        Self {
+            details: value.token.into(),
+            token: value.secret,
+        }
+    }
+}
+
+impl IntoResponse for ApiCreatedToken {
+    fn into_response(self) -> axum::response::Response {
+        Json(self).into_response()
+    }
+}
+
+pub fn routes(app_state: AppState) -> Router {
+    Router::new()
+        .route("/user/:id/tokens", post(handler_create).get(handler_list))
+        .route("/user/:id/tokens/:token_id", delete(handler_delete))
+        .with_state(app_state)
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[schema(as = CreateTokenPayload)]
+pub(super) struct CreatePayload {
+    /// What the token is for, e.g. `CI`
+    name: String,
+    scopes: Vec<ApiScope>,
+    /// Never expires when omitted
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    expires_at: Option<OffsetDateTime>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/user/{id}/tokens",
+    operation_id = "create_user_token",
+    tag = "token",
+    params(("id" = String, Path, description = "User id")),
+    request_body = CreateTokenPayload,
+    responses(
+        (status = 200, description = "The created token, including its secret", body = ApiCreatedToken),
+        (status = 400, description = "Malformed id", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "The user is neither the caller nor is the caller an admin, or the request was sent with an access token", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "The user does not exist", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid payload", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = [])),
+)]
+async fn handler_create(
+    State(AppState { token_service, .. }): State<AppState>,
+    current_user: SessionUser,
+    Path(id): Path<UserId>,
+    ApiJson(payload): ApiJson<CreatePayload>,
+) -> ApiResult<ApiCreatedToken> {
+    tracing::info!("Post /user/{id}/tokens | {payload:?}");
+
+    let input = CreateInput {
+        name: payload.name,
+        scopes: payload.scopes.into_iter().map(Scope::from).collect(),
+        expires_at: payload.expires_at,
+    };
+
+    Ok(token_service
+        .create(current_user.actor(), id, input)
+        .await?
+        .into())
+}
+
+#[utoipa::path(
+    get,
+    path = "/user/{id}/tokens",
+    operation_id = "list_user_tokens",
+    tag = "token",
+    params(("id" = String, Path, description = "User id")),
+    responses(
+        (status = 200, description = "The user's tokens, oldest first, without their secrets", body = [ApiToken]),
+        (status = 400, description = "Malformed id", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "The user is neither the caller nor is the caller an admin", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = []), ("bearer" = [])),
+)]
+async fn handler_list(
+    State(AppState { token_service, .. }): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<UserId>,
+) -> ApiResult<Json<Vec<ApiToken>>> {
+    tracing::info!("Get /user/{id}/tokens");
+
+    Ok(Json(
+        token_service
+            .list(current_user.actor(), id)
+            .await?
+            .into_iter()
+            .map(|entity| entity.into())
+            .collect(),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/user/{id}/tokens/{token_id}",
+    operation_id = "delete_user_token",
+    tag = "token",
+    params(
+        ("id" = String, Path, description = "User id"),
+        ("token_id" = String, Path, description = "Token id"),
+    ),
+    responses(
+        (status = 204, description = "The token was revoked"),
+        (status = 400, description = "Malformed id", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "The user is neither the caller nor is the caller an admin", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "The token does not exist", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = []), ("bearer" = [])),
+)]
+async fn handler_delete(
+    State(AppState { token_service, .. }): State<AppState>,
+    current_user: CurrentUser,
+    Path((id, token_id)): Path<(UserId, TokenId)>,
+) -> ApiResult<StatusCode> {
+    tracing::info!("Delete /user/{id}/tokens/{token_id}");
+
+    token_service
+        .delete(current_user.actor(), id, token_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
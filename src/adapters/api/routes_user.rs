@@ -1,34 +1,77 @@
+use std::fmt;
+
 use axum::{
-    extract::{Path, State},
-    response::IntoResponse,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
     routing::{patch, post},
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::{IntoParams, ToSchema};
 
 use crate::{
     app_state::AppState,
     domain::{
-        entities::user::User,
-        services::user_service::{CreateInput, UpdateInput},
+        entities::user::{Role, User},
+        ids::UserId,
+        services::{
+            error::FieldError,
+            user_service::{CreateInput, UpdateInput},
+        },
+        values::{Email, FirstName},
     },
 };
 
-use super::error::ApiResult;
+use super::{
+    auth::CurrentUser,
+    error::{ApiResult, ClientApiError},
+    etag::{with_etag, IfMatch, IfNoneMatch},
+    json::ApiJson,
+    path::Path,
+    query::ApiQuery,
+};
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum ApiRole {
+    /// May act on any user and todo
+    Admin,
+    /// May only act on themselves and their own todos
+    Member,
+}
+
+impl From<Role> for ApiRole {
+    fn from(value: Role) -> Self {
+        match value {
+            Role::Admin => ApiRole::Admin,
+            Role::Member => ApiRole::Member,
+        }
+    }
+}
 
-#[derive(Serialize)]
-struct ApiUser {
+#[derive(Serialize, ToSchema)]
+pub(super) struct ApiUser {
     id: String,
     email: String,
     first_name: String,
+    role: ApiRole,
+    #[serde(with = "time::serde::rfc3339")]
+    created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    updated_at: OffsetDateTime,
 }
 
 impl From<User> for ApiUser {
     fn from(value: User) -> Self {
         Self {
-            id: value.id,
+            id: value.id.to_string(),
             email: value.email,
             first_name: value.first_name,
+            role: value.role.into(),
+            created_at: value.created_at,
+            updated_at: value.updated_at,
         }
     }
 }
@@ -41,57 +84,225 @@ impl IntoResponse for ApiUser {
 
 pub fn routes(app_state: AppState) -> Router {
     Router::new()
-        .route("/user", post(handler_create))
-        .route("/user/:id", patch(handler_update).get(handler_get))
+        .route("/user", post(handler_create).get(handler_list))
+        .route(
+            "/user/:id",
+            patch(handler_update)
+                .get(handler_get)
+                .delete(handler_delete),
+        )
         .with_state(app_state)
 }
 
+// e.g. `/user?q=ada`
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ListParams {
+    /// Case-insensitive search in email and first name
+    q: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/user",
+    operation_id = "list_users",
+    tag = "user",
+    params(ListParams),
+    responses(
+        (status = 200, description = "Users ordered by email", body = [ApiUser]),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "The caller is not an admin", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = []), ("bearer" = [])),
+)]
+async fn handler_list(
+    State(AppState { user_service, .. }): State<AppState>,
+    current_user: CurrentUser,
+    ApiQuery(params): ApiQuery<ListParams>,
+) -> ApiResult<Json<Vec<ApiUser>>> {
+    tracing::info!("Get /user | {params:?}");
+
+    Ok(Json(
+        user_service
+            .list(current_user.actor(), params.q)
+            .await?
+            .into_iter()
+            .map(|entity| entity.into())
+            .collect(),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/user/{id}",
+    operation_id = "get_user",
+    tag = "user",
+    params(("id" = String, Path, description = "User id"),
+        ("If-None-Match" = Option<String>, Header, description = "`ETag` of the copy the client holds")),
+    responses(
+        (status = 200, description = "The user", body = ApiUser,
+            headers(("ETag" = String, description = "Version of the user"))),
+        (status = 304, description = "The client's copy is current"),
+        (status = 400, description = "Malformed id", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "The user does not exist", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = []), ("bearer" = [])),
+)]
 async fn handler_get(
     State(AppState { user_service, .. }): State<AppState>,
-    Path(id): Path<String>,
-) -> ApiResult<ApiUser> {
+    _current_user: CurrentUser,
+    Path(id): Path<UserId>,
+    if_none_match: IfNoneMatch,
+) -> ApiResult<Response> {
     tracing::info!("Get /user/{id}");
 
-    Ok(user_service.get(id).await?.into())
+    let user = user_service.get(id).await?;
+
+    Ok(if_none_match.respond(user.version, ApiUser::from(user)))
 }
 
-#[derive(Debug, Deserialize)]
-struct CreatePayload {
+#[derive(Deserialize, ToSchema)]
+#[schema(as = CreateUserPayload)]
+pub(super) struct CreatePayload {
     email: String,
     first_name: String,
+    /// At least 8 characters
+    #[schema(format = Password)]
+    password: String,
 }
 
+impl fmt::Debug for CreatePayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CreatePayload")
+            .field("email", &self.email)
+            .field("first_name", &self.first_name)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/user",
+    operation_id = "create_user",
+    tag = "user",
+    request_body = CreateUserPayload,
+    responses(
+        (status = 200, description = "The registered user", body = ApiUser),
+        (status = 409, description = "The email is already in use", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid payload", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
 async fn handler_create(
     State(AppState { user_service, .. }): State<AppState>,
-    Json(payload): Json<CreatePayload>,
+    ApiJson(payload): ApiJson<CreatePayload>,
 ) -> ApiResult<ApiUser> {
     tracing::info!("Post /user | {payload:?}");
 
+    let (email, first_name) = match (
+        Email::parse(payload.email),
+        FirstName::parse(payload.first_name),
+    ) {
+        (Ok(email), Ok(first_name)) => (email, first_name),
+        (email, first_name) => return Err(invalid([email.err(), first_name.err()])),
+    };
+
     let input = CreateInput {
-        email: payload.email,
-        first_name: payload.first_name,
+        email,
+        first_name,
+        password: payload.password,
+        role: Role::Member,
     };
 
     Ok(user_service.create(input).await?.into())
 }
 
-#[derive(Debug, Deserialize)]
-struct UpdatePayload {
+#[derive(Debug, Deserialize, ToSchema)]
+#[schema(as = UpdateUserPayload)]
+pub(super) struct UpdatePayload {
     email: Option<String>,
     first_name: Option<String>,
 }
 
+#[utoipa::path(
+    patch,
+    path = "/user/{id}",
+    operation_id = "update_user",
+    tag = "user",
+    params(("id" = String, Path, description = "User id"),
+        ("If-Match" = Option<String>, Header, description = "`ETag` the update is based on")),
+    request_body = UpdateUserPayload,
+    responses(
+        (status = 200, description = "The updated user", body = ApiUser,
+            headers(("ETag" = String, description = "Version of the user"))),
+        (status = 400, description = "Malformed id", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "The user is neither the caller nor is the caller an admin", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "The user does not exist", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "The email is already in use", body = Problem, content_type = "application/problem+json"),
+        (status = 412, description = "The user changed since the `If-Match` version", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid payload", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = []), ("bearer" = [])),
+)]
 async fn handler_update(
     State(AppState { user_service, .. }): State<AppState>,
-    Path(id): Path<String>,
-    Json(payload): Json<UpdatePayload>,
-) -> ApiResult<ApiUser> {
+    current_user: CurrentUser,
+    Path(id): Path<UserId>,
+    IfMatch(version): IfMatch,
+    ApiJson(payload): ApiJson<UpdatePayload>,
+) -> ApiResult<Response> {
     tracing::info!("Patch /user/{id} | {payload:?}");
 
+    let (email, first_name) = match (
+        payload.email.map(Email::parse).transpose(),
+        payload.first_name.map(FirstName::parse).transpose(),
+    ) {
+        (Ok(email), Ok(first_name)) => (email, first_name),
+        (email, first_name) => return Err(invalid([email.err(), first_name.err()])),
+    };
+
     let input = UpdateInput {
-        email: payload.email,
-        first_name: payload.first_name,
+        email,
+        first_name,
+        version,
     };
 
-    Ok(user_service.update(id, input).await?.into())
+    let user = user_service.update(current_user.actor(), id, input).await?;
+
+    Ok(with_etag(user.version, ApiUser::from(user)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/user/{id}",
+    operation_id = "delete_user",
+    tag = "user",
+    params(("id" = String, Path, description = "User id")),
+    responses(
+        (status = 204, description = "The user was deleted"),
+        (status = 400, description = "Malformed id", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not logged in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "The user is neither the caller nor is the caller an admin", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "The user does not exist", body = Problem, content_type = "application/problem+json"),
+    ),
+    security(("session" = []), ("bearer" = [])),
+)]
+async fn handler_delete(
+    State(AppState { user_service, .. }): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<UserId>,
+) -> ApiResult<StatusCode> {
+    tracing::info!("Delete /user/{id}");
+
+    user_service.delete(current_user.actor(), id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Reports every field that failed to parse at once rather than only the
+/// first.
+fn invalid<const N: usize>(errors: [Option<FieldError>; N]) -> ClientApiError {
+    ClientApiError::Validation(errors.into_iter().flatten().collect())
 }
@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use axum::{
+    extract::MatchedPath,
+    http::{Request, Response},
+};
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry_http::HeaderExtractor;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use tracing::{field, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use super::error::REQUEST_ID_HEADER;
+
+/// Opens the span every log line of a request is recorded in. `status` and
+/// `latency_ms` are filled in by [`on_response`].
+///
+/// When exported, the span continues the trace of the W3C `traceparent`
+/// header, if the caller sent one.
+pub fn make_span<B>(request: &Request<B>) -> Span {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(MatchedPath::as_str)
+        .unwrap_or("unmatched");
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    let span = tracing::info_span!(
+        "request",
+        method = %request.method(),
+        route,
+        request_id,
+        status = field::Empty,
+        latency_ms = field::Empty,
+        otel.name = format!("{} {route}", request.method()),
+        otel.kind = "server",
+        otel.status_code = field::Empty,
+    );
+    span.set_parent(TraceContextPropagator::new().extract(&HeaderExtractor(request.headers())));
+
+    span
+}
+
+pub fn on_response<B>(response: &Response<B>, latency: Duration, span: &Span) {
+    span.record("status", response.status().as_u16());
+    span.record("latency_ms", latency.as_millis() as u64);
+    if response.status().is_server_error() {
+        span.record("otel.status_code", "ERROR");
+    }
+
+    tracing::info!("Finished request");
+}
@@ -1,36 +1,260 @@
 use std::sync::Arc;
 
 use crate::{
-    domain::services::{todo_service::TodoServicePort, user_service::UserServicePort},
+    domain::{
+        clock::{Clock, SystemClock},
+        id_generator::{IdGenerator, RandomIdGenerator, TimeOrderedIdGenerator},
+        repositories::{
+            health_repository::HealthRepositoryPort, session_repository::SessionRepositoryPort,
+            todo_repository::TodoRepositoryPort, token_repository::TokenRepositoryPort,
+            user_repository::UserRepositoryPort,
+        },
+        services::{
+            auth_service::AuthServicePort, health_service::HealthServicePort,
+            todo_service::TodoServicePort, token_service::TokenServicePort,
+            user_service::UserServicePort,
+        },
+    },
     error::ServiceStartupError,
     infrastructure::{
-        repositories::{todo_repository::TodoRepository, user_repository::UserRepository},
-        Database,
+        metrics::Metrics,
+        repositories::{
+            health_repository::HealthRepository,
+            memory::{
+                health_repository::InMemoryHealthRepository,
+                session_repository::InMemorySessionRepository,
+                todo_repository::InMemoryTodoRepository, token_repository::InMemoryTokenRepository,
+                user_repository::InMemoryUserRepository, Store,
+            },
+            metered::{
+                MeteredSessionRepository, MeteredTodoRepository, MeteredTokenRepository,
+                MeteredUserRepository,
+            },
+            session_repository::SessionRepository,
+            sqlite::{
+                session_repository::SqliteSessionRepository, todo_repository::SqliteTodoRepository,
+                token_repository::SqliteTokenRepository, user_repository::SqliteUserRepository,
+            },
+            todo_repository::TodoRepository,
+            token_repository::TokenRepository,
+            user_repository::UserRepository,
+        },
+        Database, PoolConfig,
+    },
+    services::{
+        auth_service::AuthService, health_service::HealthService, todo_service::TodoService,
+        token_service::TokenService, user_service::UserService,
     },
-    services::{todo_service::TodoService, user_service::UserService},
 };
 
+/// Where the repositories keep their data.
+#[derive(Debug, Clone)]
+pub enum Storage {
+    /// Postgres or SQLite, depending on the connection string scheme.
+    Database {
+        connection_string: String,
+        pool: PoolConfig,
+    },
+    /// Process memory, lost on restart. Meant for local development.
+    Memory,
+}
+
 #[derive(Clone)]
 pub struct AppState {
+    pub auth_service: Arc<dyn AuthServicePort>,
+    pub clock: Arc<dyn Clock>,
+    pub health_service: Arc<dyn HealthServicePort>,
+    pub metrics: Metrics,
+    /// Whether the session cookie is only sent over HTTPS. On unless turned
+    /// off for local development over plain HTTP.
+    pub secure_cookies: bool,
     pub todo_service: Arc<dyn TodoServicePort>,
+    pub token_service: Arc<dyn TokenServicePort>,
     pub user_service: Arc<dyn UserServicePort>,
 }
 
 impl AppState {
-    pub async fn new(connection_string: &str) -> Result<Self, ServiceStartupError> {
-        let database = Database::new(connection_string).await?;
+    pub async fn new(storage: &Storage) -> Result<Self, ServiceStartupError> {
+        let (clock, ids) = system_clock_and_ids();
+
+        Self::with_clock_and_ids(storage, clock, ids).await
+    }
+
+    /// Like [`Self::new`], with the time read from `clock` and primary keys
+    /// taken from `ids`, e.g. fakes making tests deterministic.
+    pub async fn with_clock_and_ids(
+        storage: &Storage,
+        clock: Arc<dyn Clock>,
+        ids: Arc<dyn IdGenerator>,
+    ) -> Result<Self, ServiceStartupError> {
+        match storage {
+            Storage::Database {
+                connection_string,
+                pool,
+            } => Ok(Self::from_database(
+                Database::new(connection_string, pool).await?,
+                clock,
+                ids,
+            )),
+            Storage::Memory => {
+                tracing::warn!("Using in-memory storage, data will be lost on shutdown");
+
+                Ok(Self::in_memory_with_clock_and_ids(clock, ids))
+            }
+        }
+    }
+
+    /// Uses the repositories matching the database's backend. Session ids are
+    /// random rather than taken from `ids` since they double as secrets.
+    pub fn from_database(
+        database: Database,
+        clock: Arc<dyn Clock>,
+        ids: Arc<dyn IdGenerator>,
+    ) -> Self {
+        let health_repository = Arc::new(HealthRepository::new(database.clone()));
+        let session_ids: Arc<dyn IdGenerator> = Arc::new(RandomIdGenerator);
 
-        // Repositories
-        let todo_repository = Arc::new(TodoRepository::new(database.clone()));
-        let user_repository = Arc::new(UserRepository::new(database));
+        let app_state = match database.clone() {
+            Database::Postgres(pool) => Self::from_repositories(
+                health_repository,
+                Arc::new(SessionRepository::new(
+                    pool.clone(),
+                    clock.clone(),
+                    session_ids,
+                )),
+                Arc::new(TodoRepository::new(
+                    pool.clone(),
+                    clock.clone(),
+                    ids.clone(),
+                )),
+                Arc::new(TokenRepository::new(
+                    pool.clone(),
+                    clock.clone(),
+                    ids.clone(),
+                )),
+                Arc::new(UserRepository::new(pool, clock.clone(), ids)),
+                clock,
+            ),
+            Database::Sqlite(pool) => Self::from_repositories(
+                health_repository,
+                Arc::new(SqliteSessionRepository::new(
+                    pool.clone(),
+                    clock.clone(),
+                    session_ids,
+                )),
+                Arc::new(SqliteTodoRepository::new(
+                    pool.clone(),
+                    clock.clone(),
+                    ids.clone(),
+                )),
+                Arc::new(SqliteTokenRepository::new(
+                    pool.clone(),
+                    clock.clone(),
+                    ids.clone(),
+                )),
+                Arc::new(SqliteUserRepository::new(pool, clock.clone(), ids)),
+                clock,
+            ),
+        };
+        app_state.metrics.track_pool(database);
+
+        app_state
+    }
+
+    /// Backs every repository with a fresh in-memory store.
+    pub fn in_memory() -> Self {
+        let (clock, ids) = system_clock_and_ids();
+
+        Self::in_memory_with_clock_and_ids(clock, ids)
+    }
+
+    /// Like [`Self::in_memory`], with the time read from `clock` and primary
+    /// keys taken from `ids`.
+    pub fn in_memory_with_clock_and_ids(clock: Arc<dyn Clock>, ids: Arc<dyn IdGenerator>) -> Self {
+        let store = Store::default();
+
+        Self::from_repositories(
+            Arc::new(InMemoryHealthRepository::new(store.clone())),
+            Arc::new(InMemorySessionRepository::new(
+                store.clone(),
+                Arc::new(RandomIdGenerator),
+            )),
+            Arc::new(InMemoryTodoRepository::new(
+                store.clone(),
+                clock.clone(),
+                ids.clone(),
+            )),
+            Arc::new(InMemoryTokenRepository::new(
+                store.clone(),
+                clock.clone(),
+                ids.clone(),
+            )),
+            Arc::new(InMemoryUserRepository::new(store, clock.clone(), ids)),
+            clock,
+        )
+    }
+
+    /// Builds the services on top of arbitrary repository implementations,
+    /// e.g. test doubles. Calls to them are recorded in [`Metrics`].
+    pub fn from_repositories(
+        health_repository: Arc<dyn HealthRepositoryPort>,
+        session_repository: Arc<dyn SessionRepositoryPort>,
+        todo_repository: Arc<dyn TodoRepositoryPort>,
+        token_repository: Arc<dyn TokenRepositoryPort>,
+        user_repository: Arc<dyn UserRepositoryPort>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        let metrics = Metrics::new();
+        let session_repository = Arc::new(MeteredSessionRepository::new(
+            session_repository,
+            metrics.clone(),
+        ));
+        let todo_repository =
+            Arc::new(MeteredTodoRepository::new(todo_repository, metrics.clone()));
+        let token_repository = Arc::new(MeteredTokenRepository::new(
+            token_repository,
+            metrics.clone(),
+        ));
+        let user_repository: Arc<dyn UserRepositoryPort> =
+            Arc::new(MeteredUserRepository::new(user_repository, metrics.clone()));
 
         // Services
-        let todo_service = Arc::new(TodoService::new(todo_repository));
+        let auth_service = Arc::new(AuthService::new(
+            user_repository.clone(),
+            session_repository,
+            clock.clone(),
+        ));
+        let health_service = Arc::new(HealthService::new(health_repository));
+        let todo_service = Arc::new(TodoService::new(
+            todo_repository,
+            user_repository.clone(),
+            clock.clone(),
+        ));
+        let token_service = Arc::new(TokenService::new(
+            token_repository,
+            user_repository.clone(),
+            clock.clone(),
+        ));
         let user_service = Arc::new(UserService::new(user_repository));
 
-        Ok(Self {
+        Self {
+            auth_service,
+            clock,
+            health_service,
+            metrics,
+            secure_cookies: true,
             todo_service,
+            token_service,
             user_service,
-        })
+        }
     }
 }
+
+/// The operating system's clock, and UUIDv7 primary keys so that they sort by
+/// creation time.
+pub fn system_clock_and_ids() -> (Arc<dyn Clock>, Arc<dyn IdGenerator>) {
+    let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+    let ids = Arc::new(TimeOrderedIdGenerator::new(clock.clone()));
+
+    (clock, ids)
+}
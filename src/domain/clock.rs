@@ -0,0 +1,62 @@
+use std::sync::{Mutex, PoisonError};
+
+use time::{Duration, OffsetDateTime};
+
+/// The source of the current time, so that services and repositories can be
+/// driven by a fake one in tests.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> OffsetDateTime;
+}
+
+/// The operating system's clock, in UTC.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc()
+    }
+}
+
+/// Always tells the same time.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub OffsetDateTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> OffsetDateTime {
+        self.0
+    }
+}
+
+/// Starts at a given time and moves forward by `step` after every reading,
+/// so consecutive events get distinct, increasing timestamps. Can also be
+/// moved by hand, e.g. past the expiry of a session.
+#[derive(Debug)]
+pub struct SteppingClock {
+    now: Mutex<OffsetDateTime>,
+    step: Duration,
+}
+
+impl SteppingClock {
+    pub fn new(start: OffsetDateTime, step: Duration) -> Self {
+        Self {
+            now: Mutex::new(start),
+            step,
+        }
+    }
+
+    /// Moves the clock forward by `duration` without reading it.
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner) += duration;
+    }
+}
+
+impl Clock for SteppingClock {
+    fn now(&self) -> OffsetDateTime {
+        let mut now = self.now.lock().unwrap_or_else(PoisonError::into_inner);
+        let current = *now;
+        *now += self.step;
+
+        current
+    }
+}
@@ -1,2 +1,4 @@
+pub mod session;
 pub mod todo;
+pub mod token;
 pub mod user;
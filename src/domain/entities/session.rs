@@ -0,0 +1,9 @@
+use time::OffsetDateTime;
+
+use crate::domain::ids::UserId;
+
+pub struct Session {
+    pub id: String,
+    pub user_id: UserId,
+    pub expires_at: OffsetDateTime,
+}
@@ -1,5 +1,51 @@
+use std::str::FromStr;
+
+use time::OffsetDateTime;
+
+use crate::domain::ids::{TodoId, UserId};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Priority {
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl Priority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Priority::Low => "low",
+            Priority::Medium => "medium",
+            Priority::High => "high",
+        }
+    }
+}
+
+impl FromStr for Priority {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "low" => Ok(Priority::Low),
+            "medium" => Ok(Priority::Medium),
+            "high" => Ok(Priority::High),
+            _ => Err(()),
+        }
+    }
+}
+
 pub struct Todo {
-    pub id: String,
+    pub id: TodoId,
+    pub owner_id: UserId,
     pub title: String,
     pub description: String,
+    pub priority: Priority,
+    pub due_at: Option<OffsetDateTime>,
+    pub completed_at: Option<OffsetDateTime>,
+    pub deleted_at: Option<OffsetDateTime>,
+    /// Incremented on every change, for optimistic concurrency control.
+    pub version: i64,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
 }
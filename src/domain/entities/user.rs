@@ -1,5 +1,45 @@
+use std::str::FromStr;
+
+use time::OffsetDateTime;
+
+use crate::domain::ids::UserId;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Role {
+    Admin,
+    #[default]
+    Member,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Member => "member",
+        }
+    }
+}
+
+impl FromStr for Role {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "admin" => Ok(Role::Admin),
+            "member" => Ok(Role::Member),
+            _ => Err(()),
+        }
+    }
+}
+
 pub struct User {
-    pub id: String,
+    pub id: UserId,
     pub email: String,
     pub first_name: String,
+    pub password_hash: Option<String>,
+    pub role: Role,
+    /// Incremented on every change, for optimistic concurrency control.
+    pub version: i64,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
 }
@@ -0,0 +1,74 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex, PoisonError,
+};
+
+use uuid::{timestamp::context::ContextV7, Timestamp, Uuid};
+
+use super::clock::Clock;
+
+/// Hands out primary keys, so that repositories can be given predictable
+/// ones in tests.
+pub trait IdGenerator: Send + Sync {
+    fn generate(&self) -> Uuid;
+}
+
+/// Random UUIDv4s. Used where an id doubles as a secret, like session ids.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomIdGenerator;
+
+impl IdGenerator for RandomIdGenerator {
+    fn generate(&self) -> Uuid {
+        Uuid::new_v4()
+    }
+}
+
+/// UUIDv7s stamped with the time of `clock`, so that primary keys sort by
+/// creation time, which keeps B-tree inserts local. Ids generated within the
+/// same millisecond are still ordered, by a counter.
+pub struct TimeOrderedIdGenerator {
+    clock: Arc<dyn Clock>,
+    context: Mutex<ContextV7>,
+}
+
+impl TimeOrderedIdGenerator {
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            clock,
+            context: Mutex::new(ContextV7::new()),
+        }
+    }
+}
+
+impl IdGenerator for TimeOrderedIdGenerator {
+    fn generate(&self) -> Uuid {
+        let now = self.clock.now();
+        let context = self.context.lock().unwrap_or_else(PoisonError::into_inner);
+        // UUIDv7 cannot represent times before the Unix epoch
+        let seconds = u64::try_from(now.unix_timestamp()).unwrap_or_default();
+
+        Uuid::new_v7(Timestamp::from_unix(&*context, seconds, now.nanosecond()))
+    }
+}
+
+/// Always returns the same id, for tests creating a single record.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedIdGenerator(pub Uuid);
+
+impl IdGenerator for FixedIdGenerator {
+    fn generate(&self) -> Uuid {
+        self.0
+    }
+}
+
+/// Counts up from `00000000-0000-0000-0000-000000000001`, one id per call.
+#[derive(Debug, Default)]
+pub struct SequentialIdGenerator {
+    last: AtomicU64,
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    fn generate(&self) -> Uuid {
+        Uuid::from_u128(u128::from(self.last.fetch_add(1, Ordering::Relaxed) + 1))
+    }
+}
@@ -0,0 +1,80 @@
+//! Typed ids, parsed where they enter the application so that a todo id
+//! can't be passed where a user id is expected, and so that repositories
+//! never see a malformed one.
+
+use std::{error::Error, fmt, str::FromStr};
+
+use serde::{de, Deserialize, Deserializer};
+use uuid::Uuid;
+
+/// A string that is not a valid id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidId {
+    kind: &'static str,
+    value: String,
+}
+
+impl fmt::Display for InvalidId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is not a valid {} id", self.value, self.kind)
+    }
+}
+
+impl Error for InvalidId {}
+
+macro_rules! uuid_id {
+    ($(#[$meta:meta])* $name:ident, $kind:literal) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(Uuid);
+
+        impl $name {
+            pub fn as_uuid(&self) -> Uuid {
+                self.0
+            }
+        }
+
+        impl From<Uuid> for $name {
+            fn from(value: Uuid) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for Uuid {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = InvalidId;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                Uuid::from_str(value).map(Self).map_err(|_| InvalidId {
+                    kind: $kind,
+                    value: value.to_string(),
+                })
+            }
+        }
+
+        /// Hyphenated and lowercase, the form ids are stored and served in.
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.hyphenated().fmt(f)
+            }
+        }
+
+        /// Parsed like [`FromStr`], so a malformed id names its kind.
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+
+                Self::from_str(&value).map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+uuid_id!(TodoId, "todo");
+uuid_id!(UserId, "user");
+uuid_id!(TokenId, "token");
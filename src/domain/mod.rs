@@ -1,3 +1,7 @@
+pub mod clock;
 pub mod entities;
+pub mod id_generator;
+pub mod ids;
 pub mod repositories;
 pub mod services;
+pub mod values;
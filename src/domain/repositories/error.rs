@@ -1,7 +1,16 @@
 #[derive(Debug)]
 pub enum RepositoryError {
     NotFound,
+    /// A session or token id that is not a UUID.
     InvalidUuid,
+    /// The row exists but is no longer at the version the update expected.
+    VersionMismatch,
+    /// A unique constraint on `field` rejected the write.
+    Conflict {
+        field: String,
+    },
+    /// The backing store could not be reached.
+    Unavailable,
     Unknown,
 }
 
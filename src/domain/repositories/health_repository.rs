@@ -0,0 +1,22 @@
+use axum::async_trait;
+
+use super::error::RepositoryResult;
+
+/// State of the storage behind the other repositories.
+#[derive(Debug, Clone)]
+pub struct StorageHealth {
+    pub backend: &'static str,
+    /// Open connections, idle or in use.
+    pub pool_size: u32,
+    pub pool_idle: usize,
+    /// Latest applied migration, `None` if the storage has no migrations.
+    pub migration_version: Option<i64>,
+    /// Whether migrations shipped with this build have not been applied yet.
+    pub migrations_pending: bool,
+}
+
+#[async_trait]
+pub trait HealthRepositoryPort: Send + Sync {
+    /// Round trips to the storage, failing when it can't serve queries.
+    async fn check(&self) -> RepositoryResult<StorageHealth>;
+}
@@ -1,3 +1,7 @@
 pub mod error;
+pub mod health_repository;
+pub mod pagination;
+pub mod session_repository;
 pub mod todo_repository;
+pub mod token_repository;
 pub mod user_repository;
@@ -0,0 +1,31 @@
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Sort key of the last item on a page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CursorValue {
+    Timestamp(OffsetDateTime),
+    Text(String),
+}
+
+/// Position to resume a keyset-paginated listing from. `sort_by` and
+/// `direction` must match the query the cursor is used with.
+#[derive(Debug, Clone)]
+pub struct Cursor<S> {
+    pub sort_by: S,
+    pub direction: SortDirection,
+    pub value: CursorValue,
+    pub id: String,
+}
+
+#[derive(Debug)]
+pub struct Page<T, S> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<Cursor<S>>,
+}
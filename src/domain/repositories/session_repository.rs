@@ -0,0 +1,19 @@
+use axum::async_trait;
+use time::OffsetDateTime;
+
+use crate::domain::{entities::session::Session, ids::UserId};
+
+use super::error::RepositoryResult;
+
+#[derive(Debug)]
+pub struct CreateInput {
+    pub user_id: UserId,
+    pub expires_at: OffsetDateTime,
+}
+
+#[async_trait]
+pub trait SessionRepositoryPort: Send + Sync {
+    async fn find_by_id(&self, id: String) -> RepositoryResult<Session>;
+    async fn create(&self, input: CreateInput) -> RepositoryResult<Session>;
+    async fn delete(&self, id: String) -> RepositoryResult<()>;
+}
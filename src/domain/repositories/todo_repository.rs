@@ -1,26 +1,83 @@
 use axum::async_trait;
+use time::OffsetDateTime;
 
-use crate::domain::entities::todo::Todo;
+use crate::domain::{
+    entities::todo::{Priority, Todo},
+    ids::{TodoId, UserId},
+    values::TodoTitle,
+};
 
-use super::error::RepositoryResult;
+use super::{
+    error::RepositoryResult,
+    pagination::{Cursor, Page, SortDirection},
+};
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TodoSortField {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    Title,
+}
+
+pub type TodoCursor = Cursor<TodoSortField>;
+pub type TodoPage = Page<Todo, TodoSortField>;
+
+#[derive(Debug)]
+pub struct ListQuery {
+    pub owner_id: UserId,
+    pub completed: Option<bool>,
+    /// Case-insensitive substring match on title or description.
+    pub search: Option<String>,
+    pub created_after: Option<OffsetDateTime>,
+    pub created_before: Option<OffsetDateTime>,
+    pub updated_after: Option<OffsetDateTime>,
+    pub updated_before: Option<OffsetDateTime>,
+    pub sort_by: TodoSortField,
+    pub direction: SortDirection,
+    pub after: Option<TodoCursor>,
+    pub limit: u32,
+}
+
+/// `None` leaves a field untouched. For nullable columns `Some(None)` clears
+/// the stored value.
 #[derive(Debug)]
 pub struct UpdateInput {
-    pub id: String,
-    pub title: Option<String>,
+    pub id: TodoId,
+    pub title: Option<TodoTitle>,
     pub description: Option<String>,
+    pub priority: Option<Priority>,
+    pub due_at: Option<Option<OffsetDateTime>>,
+    pub completed_at: Option<Option<OffsetDateTime>>,
+    /// Only update the todo if it is still at this version.
+    pub version: Option<i64>,
 }
 
 #[derive(Debug)]
 pub struct CreateInput {
-    pub title: String,
+    pub owner_id: UserId,
+    pub title: TodoTitle,
     pub description: String,
+    pub priority: Priority,
+    pub due_at: Option<OffsetDateTime>,
 }
 
 #[async_trait]
 pub trait TodoRepositoryPort: Send + Sync {
-    async fn list(&self) -> RepositoryResult<Vec<Todo>>;
-    async fn find_by_id(&self, id: String) -> RepositoryResult<Todo>;
+    async fn list(&self, query: ListQuery) -> RepositoryResult<TodoPage>;
+    async fn find_by_id(&self, id: TodoId) -> RepositoryResult<Todo>;
+    async fn find_by_owner_and_id(&self, owner_id: UserId, id: TodoId) -> RepositoryResult<Todo>;
     async fn update_one(&self, input: UpdateInput) -> RepositoryResult<Todo>;
     async fn create(&self, input: CreateInput) -> RepositoryResult<Todo>;
+    async fn delete(&self, id: TodoId) -> RepositoryResult<()>;
+    async fn list_deleted_by_owner(&self, owner_id: UserId) -> RepositoryResult<Vec<Todo>>;
+    /// The todo, provided it is in the trash of `owner_id`.
+    async fn find_deleted_by_owner_and_id(
+        &self,
+        owner_id: UserId,
+        id: TodoId,
+    ) -> RepositoryResult<Todo>;
+    async fn restore(&self, id: TodoId) -> RepositoryResult<Todo>;
+    /// Permanently removes todos soft deleted before `deleted_before`.
+    async fn purge_deleted(&self, deleted_before: OffsetDateTime) -> RepositoryResult<u64>;
 }
@@ -0,0 +1,30 @@
+use axum::async_trait;
+use time::OffsetDateTime;
+
+use crate::domain::{
+    entities::token::{Scope, Token},
+    ids::{TokenId, UserId},
+};
+
+use super::error::RepositoryResult;
+
+#[derive(Debug)]
+pub struct CreateInput {
+    pub user_id: UserId,
+    pub name: String,
+    /// SHA-256 of the token, hex encoded.
+    pub token_hash: String,
+    pub scopes: Vec<Scope>,
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+#[async_trait]
+pub trait TokenRepositoryPort: Send + Sync {
+    async fn find_by_hash(&self, token_hash: String) -> RepositoryResult<Token>;
+    /// Oldest first.
+    async fn list_by_user(&self, user_id: UserId) -> RepositoryResult<Vec<Token>>;
+    async fn create(&self, input: CreateInput) -> RepositoryResult<Token>;
+    async fn touch(&self, id: TokenId, used_at: OffsetDateTime) -> RepositoryResult<()>;
+    /// Fails with `NotFound` unless the token belongs to `user_id`.
+    async fn delete(&self, user_id: UserId, id: TokenId) -> RepositoryResult<()>;
+}
@@ -1,25 +1,54 @@
+use std::fmt;
+
 use axum::async_trait;
+use time::OffsetDateTime;
 
-use crate::domain::entities::user::User;
+use crate::domain::{
+    entities::user::{Role, User},
+    ids::UserId,
+    values::{Email, FirstName},
+};
 
 use super::error::RepositoryResult;
 
+/// `None` leaves a field untouched.
 #[derive(Debug)]
 pub struct UpdateInput {
-    pub email: String,
-    pub first_name: String,
+    pub email: Option<Email>,
+    pub first_name: Option<FirstName>,
+    /// Only update the user if they are still at this version.
+    pub version: Option<i64>,
 }
 
-#[derive(Debug)]
 pub struct CreateInput {
-    pub email: String,
-    pub first_name: String,
+    pub email: Email,
+    pub first_name: FirstName,
+    pub password_hash: String,
+    pub role: Role,
+}
+
+impl fmt::Debug for CreateInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CreateInput")
+            .field("email", &self.email)
+            .field("first_name", &self.first_name)
+            .field("password_hash", &"<redacted>")
+            .field("role", &self.role)
+            .finish()
+    }
 }
 
 #[async_trait]
 pub trait UserRepositoryPort: Send + Sync {
-    async fn find_by_id(&self, id: String) -> RepositoryResult<User>;
+    async fn find_by_id(&self, id: UserId) -> RepositoryResult<User>;
     async fn find_by_email(&self, email: String) -> RepositoryResult<Option<User>>;
-    async fn update_one(&self, id: String, input: UpdateInput) -> RepositoryResult<User>;
+    /// Users not soft deleted, ordered by email. `search` is a
+    /// case-insensitive substring match on email or first name.
+    async fn list(&self, search: Option<String>) -> RepositoryResult<Vec<User>>;
+    async fn update_one(&self, id: UserId, input: UpdateInput) -> RepositoryResult<User>;
     async fn create(&self, input: CreateInput) -> RepositoryResult<User>;
+    async fn set_role(&self, id: UserId, role: Role) -> RepositoryResult<User>;
+    async fn delete(&self, id: UserId) -> RepositoryResult<()>;
+    /// Permanently removes users soft deleted before `deleted_before`.
+    async fn purge_deleted(&self, deleted_before: OffsetDateTime) -> RepositoryResult<u64>;
 }
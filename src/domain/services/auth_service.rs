@@ -0,0 +1,28 @@
+use std::fmt;
+
+use axum::async_trait;
+
+use crate::domain::entities::{session::Session, user::User};
+
+use super::error::ServiceResult;
+
+pub struct LoginInput {
+    pub email: String,
+    pub password: String,
+}
+
+impl fmt::Debug for LoginInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LoginInput")
+            .field("email", &self.email)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+#[async_trait]
+pub trait AuthServicePort: Sync + Send {
+    async fn login(&self, input: LoginInput) -> ServiceResult<(Session, User)>;
+    async fn logout(&self, session_id: String) -> ServiceResult<()>;
+    async fn authenticate(&self, session_id: String) -> ServiceResult<User>;
+}
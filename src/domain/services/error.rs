@@ -1,20 +1,59 @@
 use crate::domain::repositories::error::RepositoryError;
 
+/// A rejected input field and the reason it was rejected.
+#[derive(Debug, Clone)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum ServiceError {
     NotFound,
+    /// Malformed input that is not tied to a single field, e.g. an invalid id.
+    BadInput(String),
+    Validation(Vec<FieldError>),
+    EmailTaken,
+    Conflict(String),
+    /// The resource changed since the version the caller based its request
+    /// on.
+    PreconditionFailed,
+    Unauthorized,
+    /// The caller is authenticated but not allowed to do this.
+    Forbidden,
+    /// A dependency such as the database is unreachable; retrying may succeed.
+    Unavailable,
     Unknown,
-    BadInput,
 }
 
 pub type ServiceResult<T> = Result<T, ServiceError>;
 
+impl From<FieldError> for ServiceError {
+    fn from(value: FieldError) -> Self {
+        ServiceError::Validation(vec![value])
+    }
+}
+
 impl From<RepositoryError> for ServiceError {
     fn from(value: RepositoryError) -> Self {
         match value {
             RepositoryError::NotFound => ServiceError::NotFound,
+            RepositoryError::Unavailable => ServiceError::Unavailable,
             RepositoryError::Unknown => ServiceError::Unknown,
-            RepositoryError::InvalidUuid => ServiceError::BadInput,
+            RepositoryError::InvalidUuid => ServiceError::BadInput("Invalid identifier".into()),
+            RepositoryError::VersionMismatch => ServiceError::PreconditionFailed,
+            RepositoryError::Conflict { field } => {
+                ServiceError::Conflict(format!("The {field} is already in use"))
+            }
         }
     }
 }
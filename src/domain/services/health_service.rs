@@ -0,0 +1,24 @@
+use std::time::Duration;
+
+use axum::async_trait;
+
+use crate::domain::repositories::health_repository::StorageHealth;
+
+use super::error::ServiceResult;
+
+#[derive(Debug)]
+pub struct Status {
+    pub version: &'static str,
+    pub uptime: Duration,
+    pub storage: StorageHealth,
+}
+
+#[async_trait]
+pub trait HealthServicePort: Sync + Send {
+    /// Whether requests can be served: the storage is reachable, fully
+    /// migrated and the server isn't shutting down.
+    async fn readiness(&self) -> ServiceResult<()>;
+    async fn status(&self) -> ServiceResult<Status>;
+    /// Fails readiness from now on so load balancers stop sending traffic.
+    fn begin_shutdown(&self);
+}
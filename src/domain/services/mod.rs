@@ -1,3 +1,7 @@
+pub mod auth_service;
 pub mod error;
+pub mod health_service;
+pub mod policy;
 pub mod todo_service;
+pub mod token_service;
 pub mod user_service;
@@ -0,0 +1,60 @@
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::user::{Role, User},
+    ids::UserId,
+};
+
+use super::error::{ServiceError, ServiceResult};
+
+/// Who a service call is made on behalf of. Members may only act on
+/// themselves and what they own, admins on anything.
+#[derive(Debug, Clone)]
+pub struct Actor {
+    pub user_id: UserId,
+    pub role: Role,
+}
+
+impl Actor {
+    /// The operator running the CLI or a background job, allowed everything
+    /// an admin is. Its id is the nil UUID, which no user has.
+    pub fn system() -> Self {
+        Self {
+            user_id: UserId::from(Uuid::nil()),
+            role: Role::Admin,
+        }
+    }
+
+    pub fn is_admin(&self) -> bool {
+        self.role == Role::Admin
+    }
+
+    /// Fails with `Forbidden` unless the actor is `user_id` or an admin.
+    pub fn ensure_can_manage(&self, user_id: UserId) -> ServiceResult<()> {
+        if self.is_admin() || self.user_id == user_id {
+            return Ok(());
+        }
+
+        tracing::warn!("{} may not act on behalf of {user_id}", self.user_id);
+        Err(ServiceError::Forbidden)
+    }
+
+    /// Fails with `Forbidden` unless the actor is an admin.
+    pub fn ensure_admin(&self) -> ServiceResult<()> {
+        if self.is_admin() {
+            return Ok(());
+        }
+
+        tracing::warn!("{} is not an admin", self.user_id);
+        Err(ServiceError::Forbidden)
+    }
+}
+
+impl From<&User> for Actor {
+    fn from(user: &User) -> Self {
+        Self {
+            user_id: user.id,
+            role: user.role,
+        }
+    }
+}
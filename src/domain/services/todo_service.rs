@@ -1,25 +1,75 @@
 use axum::async_trait;
+use time::OffsetDateTime;
 
-use crate::domain::entities::todo::Todo;
+use crate::domain::{
+    entities::todo::{Priority, Todo},
+    ids::{TodoId, UserId},
+    repositories::{
+        pagination::SortDirection,
+        todo_repository::{TodoCursor, TodoPage, TodoSortField},
+    },
+    values::TodoTitle,
+};
 
-use super::error::ServiceResult;
+use super::{error::ServiceResult, policy::Actor};
 
 #[derive(Debug)]
 pub struct CreateInput {
-    pub title: String,
+    pub owner_id: UserId,
+    pub title: TodoTitle,
     pub description: String,
+    pub priority: Option<Priority>,
+    pub due_at: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Default)]
+pub struct ListInput {
+    pub completed: Option<bool>,
+    pub search: Option<String>,
+    pub created_after: Option<OffsetDateTime>,
+    pub created_before: Option<OffsetDateTime>,
+    pub updated_after: Option<OffsetDateTime>,
+    pub updated_before: Option<OffsetDateTime>,
+    pub sort_by: TodoSortField,
+    pub direction: SortDirection,
+    pub after: Option<TodoCursor>,
+    /// Defaults to 50 and is capped at 100.
+    pub limit: Option<u32>,
 }
 
 #[derive(Debug)]
 pub struct UpdateInput {
-    pub title: Option<String>,
+    pub title: Option<TodoTitle>,
     pub description: Option<String>,
+    pub priority: Option<Priority>,
+    pub due_at: Option<Option<OffsetDateTime>>,
+    /// The version the caller last saw, `None` to update unconditionally.
+    pub version: Option<i64>,
 }
 
+/// Todos are only accessible to their owner and to admins, others get
+/// `Forbidden`.
 #[async_trait]
 pub trait TodoServicePort: Sync + Send {
-    async fn list(&self) -> ServiceResult<Vec<Todo>>;
-    async fn get(&self, todo_id: String) -> ServiceResult<Todo>;
-    async fn update(&self, id: String, update: UpdateInput) -> ServiceResult<Todo>;
-    async fn create(&self, input: CreateInput) -> ServiceResult<Todo>;
+    async fn list_for_user(
+        &self,
+        actor: Actor,
+        user_id: UserId,
+        input: ListInput,
+    ) -> ServiceResult<TodoPage>;
+    async fn get(&self, actor: Actor, todo_id: TodoId) -> ServiceResult<Todo>;
+    async fn get_for_user(
+        &self,
+        actor: Actor,
+        user_id: UserId,
+        todo_id: TodoId,
+    ) -> ServiceResult<Todo>;
+    async fn update(&self, actor: Actor, id: TodoId, update: UpdateInput) -> ServiceResult<Todo>;
+    async fn create(&self, actor: Actor, input: CreateInput) -> ServiceResult<Todo>;
+    async fn complete(&self, actor: Actor, id: TodoId) -> ServiceResult<Todo>;
+    async fn reopen(&self, actor: Actor, id: TodoId) -> ServiceResult<Todo>;
+    async fn delete(&self, actor: Actor, id: TodoId) -> ServiceResult<()>;
+    async fn list_trash_for_user(&self, actor: Actor, user_id: UserId) -> ServiceResult<Vec<Todo>>;
+    async fn restore(&self, actor: Actor, id: TodoId) -> ServiceResult<Todo>;
+    async fn purge_deleted(&self, deleted_before: OffsetDateTime) -> ServiceResult<u64>;
 }
@@ -0,0 +1,51 @@
+use std::fmt;
+
+use axum::async_trait;
+use time::OffsetDateTime;
+
+use crate::domain::{
+    entities::{
+        token::{Scope, Token},
+        user::User,
+    },
+    ids::{TokenId, UserId},
+};
+
+use super::{error::ServiceResult, policy::Actor};
+
+#[derive(Debug)]
+pub struct CreateInput {
+    pub name: String,
+    pub scopes: Vec<Scope>,
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+/// A token as returned on creation, the only time `secret` is known.
+pub struct CreatedToken {
+    pub token: Token,
+    pub secret: String,
+}
+
+impl fmt::Debug for CreatedToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CreatedToken")
+            .field("id", &self.token.id)
+            .field("secret", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Tokens are managed by their owner, or by an admin.
+#[async_trait]
+pub trait TokenServicePort: Sync + Send {
+    async fn create(
+        &self,
+        actor: Actor,
+        user_id: UserId,
+        input: CreateInput,
+    ) -> ServiceResult<CreatedToken>;
+    async fn list(&self, actor: Actor, user_id: UserId) -> ServiceResult<Vec<Token>>;
+    async fn delete(&self, actor: Actor, user_id: UserId, id: TokenId) -> ServiceResult<()>;
+    /// Resolves a token sent by a client, recording that it was used.
+    async fn authenticate(&self, secret: String) -> ServiceResult<(Token, User)>;
+}
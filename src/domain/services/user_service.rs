@@ -1,24 +1,53 @@
+use std::fmt;
+
 use axum::async_trait;
+use time::OffsetDateTime;
 
-use crate::domain::entities::user::User;
+use crate::domain::{
+    entities::user::{Role, User},
+    ids::UserId,
+    values::{Email, FirstName},
+};
 
-use super::error::ServiceResult;
+use super::{error::ServiceResult, policy::Actor};
 
-#[derive(Debug)]
 pub struct CreateInput {
-    pub email: String,
-    pub first_name: String,
+    pub email: Email,
+    pub first_name: FirstName,
+    pub password: String,
+    /// Registration over HTTP always asks for `Role::Member`.
+    pub role: Role,
+}
+
+impl fmt::Debug for CreateInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CreateInput")
+            .field("email", &self.email)
+            .field("first_name", &self.first_name)
+            .field("password", &"<redacted>")
+            .field("role", &self.role)
+            .finish()
+    }
 }
 
 #[derive(Debug)]
 pub struct UpdateInput {
-    pub email: Option<String>,
-    pub first_name: Option<String>,
+    pub email: Option<Email>,
+    pub first_name: Option<FirstName>,
+    /// The version the caller last saw, `None` to update unconditionally.
+    pub version: Option<i64>,
 }
 
+/// Users may only modify themselves, admins anyone. Listing users and
+/// changing roles is reserved to admins.
 #[async_trait]
 pub trait UserServicePort: Sync + Send {
-    async fn get(&self, todo_id: String) -> ServiceResult<User>;
-    async fn update(&self, id: String, update: UpdateInput) -> ServiceResult<User>;
+    async fn get(&self, id: UserId) -> ServiceResult<User>;
+    /// Users whose email or first name contains `search`, ignoring case.
+    async fn list(&self, actor: Actor, search: Option<String>) -> ServiceResult<Vec<User>>;
+    async fn update(&self, actor: Actor, id: UserId, update: UpdateInput) -> ServiceResult<User>;
     async fn create(&self, input: CreateInput) -> ServiceResult<User>;
+    async fn set_role(&self, actor: Actor, id: UserId, role: Role) -> ServiceResult<User>;
+    async fn delete(&self, actor: Actor, id: UserId) -> ServiceResult<()>;
+    async fn purge_deleted(&self, deleted_before: OffsetDateTime) -> ServiceResult<u64>;
 }
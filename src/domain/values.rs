@@ -0,0 +1,89 @@
+//! Value objects for user input with rules beyond its type. They can only be
+//! built through `parse`, so a service receiving one knows it is valid.
+
+use super::services::error::FieldError;
+
+const MAX_EMAIL_LENGTH: usize = 254;
+const MAX_FIRST_NAME_LENGTH: usize = 100;
+const MAX_TODO_TITLE_LENGTH: usize = 200;
+
+/// Surrounding whitespace removed, between 1 and `max` characters.
+fn parse_text(field: &str, value: String, max: usize) -> Result<String, FieldError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(FieldError::new(field, "must not be empty"));
+    }
+    if value.chars().count() > max {
+        return Err(FieldError::new(
+            field,
+            format!("must be at most {max} characters long"),
+        ));
+    }
+
+    Ok(value.to_string())
+}
+
+/// An email address of the form `local@domain.tld`. Its case is kept as
+/// entered, uniqueness is checked ignoring case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Email(String);
+
+impl Email {
+    pub fn parse(value: impl Into<String>) -> Result<Self, FieldError> {
+        let value = parse_text("email", value.into(), MAX_EMAIL_LENGTH)?;
+
+        let well_formed = value.split_once('@').is_some_and(|(local, domain)| {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && domain.split('.').all(|label| !label.is_empty())
+        }) && !value.contains(char::is_whitespace);
+        if !well_formed {
+            return Err(FieldError::new("email", "must be a valid email address"));
+        }
+
+        Ok(Self(value))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FirstName(String);
+
+impl FirstName {
+    pub fn parse(value: impl Into<String>) -> Result<Self, FieldError> {
+        parse_text("first_name", value.into(), MAX_FIRST_NAME_LENGTH).map(Self)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodoTitle(String);
+
+impl TodoTitle {
+    pub fn parse(value: impl Into<String>) -> Result<Self, FieldError> {
+        parse_text("title", value.into(), MAX_TODO_TITLE_LENGTH).map(Self)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
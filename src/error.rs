@@ -6,4 +6,5 @@ pub enum ServiceStartupError {
     ServiceStartup { addr: SocketAddr },
     DatabaseConnection,
     DatabaseMigration,
+    Telemetry,
 }
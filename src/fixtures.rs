@@ -0,0 +1,151 @@
+//! Sample users and todos loaded by the `seed` command, through the services
+//! so that passwords are hashed and inputs validated like over HTTP.
+
+use std::{fmt, str::FromStr};
+
+use serde::Deserialize;
+
+use crate::{
+    app_state::AppState,
+    domain::{
+        entities::{todo::Priority, user::Role},
+        services::{
+            error::{ServiceError, ServiceResult},
+            policy::Actor,
+            todo_service, user_service,
+        },
+        values::{Email, FirstName, TodoTitle},
+    },
+};
+
+const DEMO: &str = include_str!("../fixtures/demo.json");
+
+#[derive(Deserialize, Debug)]
+pub struct Fixtures {
+    pub users: Vec<UserFixture>,
+}
+
+#[derive(Deserialize)]
+pub struct UserFixture {
+    pub email: String,
+    pub first_name: String,
+    pub password: String,
+    #[serde(default)]
+    pub admin: bool,
+    #[serde(default)]
+    pub todos: Vec<TodoFixture>,
+}
+
+impl fmt::Debug for UserFixture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UserFixture")
+            .field("email", &self.email)
+            .field("first_name", &self.first_name)
+            .field("password", &"<redacted>")
+            .field("admin", &self.admin)
+            .field("todos", &self.todos)
+            .finish()
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TodoFixture {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    /// `low`, `medium` or `high`.
+    pub priority: Option<String>,
+    #[serde(default)]
+    pub completed: bool,
+}
+
+/// What [`Fixtures::load`] created.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Seeded {
+    pub users: usize,
+    pub todos: usize,
+    /// Users whose email was already registered, left untouched along with
+    /// their todos.
+    pub skipped_users: usize,
+}
+
+// Constructor
+impl Fixtures {
+    /// The fixtures bundled with the binary.
+    pub fn demo() -> Self {
+        Self::from_json(DEMO).expect("bundled fixtures are valid")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// Methods
+impl Fixtures {
+    /// Creates the users and their todos. Users that already exist are
+    /// skipped, so seeding twice is harmless.
+    pub async fn load(&self, app_state: &AppState) -> ServiceResult<Seeded> {
+        let mut seeded = Seeded::default();
+
+        for fixture in &self.users {
+            let created = app_state
+                .user_service
+                .create(user_service::CreateInput {
+                    email: Email::parse(fixture.email.clone())?,
+                    first_name: FirstName::parse(fixture.first_name.clone())?,
+                    password: fixture.password.clone(),
+                    role: if fixture.admin {
+                        Role::Admin
+                    } else {
+                        Role::Member
+                    },
+                })
+                .await;
+            let user = match created {
+                Ok(user) => user,
+                Err(ServiceError::EmailTaken) => {
+                    tracing::info!("Skipping {}, already registered", fixture.email);
+                    seeded.skipped_users += 1;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            seeded.users += 1;
+
+            for todo in &fixture.todos {
+                let priority = todo
+                    .priority
+                    .as_deref()
+                    .map(|priority| {
+                        Priority::from_str(priority).map_err(|_| {
+                            ServiceError::BadInput(format!("Unknown priority '{priority}'"))
+                        })
+                    })
+                    .transpose()?;
+                let created = app_state
+                    .todo_service
+                    .create(
+                        Actor::from(&user),
+                        todo_service::CreateInput {
+                            owner_id: user.id,
+                            title: TodoTitle::parse(todo.title.clone())?,
+                            description: todo.description.clone(),
+                            priority,
+                            due_at: None,
+                        },
+                    )
+                    .await?;
+                if todo.completed {
+                    app_state
+                        .todo_service
+                        .complete(Actor::from(&user), created.id)
+                        .await?;
+                }
+                seeded.todos += 1;
+            }
+        }
+
+        Ok(seeded)
+    }
+}
@@ -0,0 +1,155 @@
+use std::{
+    future::Future,
+    sync::{Arc, OnceLock},
+    time::{Duration, Instant},
+};
+
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+
+use crate::domain::repositories::error::RepositoryResult;
+
+use super::Database;
+
+/// Prometheus metrics of one app. Cloning is cheap and clones share the
+/// registry, so every layer records into the same set.
+#[derive(Clone)]
+pub struct Metrics {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    repository_call_duration: HistogramVec,
+    pool_connections: IntGaugeVec,
+    database: OnceLock<Database>,
+}
+
+// Constructor
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let http_requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP requests handled"),
+            &["method", "route", "status"],
+        )
+        .expect("metric options are valid");
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "Time until the response head was ready",
+            ),
+            &["method", "route"],
+        )
+        .expect("metric options are valid");
+        let repository_call_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "repository_call_duration_seconds",
+                "Time spent in repository methods",
+            ),
+            &["repository", "method", "outcome"],
+        )
+        .expect("metric options are valid");
+        let pool_connections = IntGaugeVec::new(
+            Opts::new("db_pool_connections", "Database pool connections"),
+            &["state"],
+        )
+        .expect("metric options are valid");
+
+        for collector in [
+            Box::new(http_requests.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(http_request_duration.clone()),
+            Box::new(repository_call_duration.clone()),
+            Box::new(pool_connections.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("metric names are unique");
+        }
+
+        Self {
+            inner: Arc::new(Inner {
+                registry,
+                http_requests,
+                http_request_duration,
+                repository_call_duration,
+                pool_connections,
+                database: OnceLock::new(),
+            }),
+        }
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Methods
+impl Metrics {
+    /// Reports the pool's connection counts on every scrape.
+    pub fn track_pool(&self, database: Database) {
+        if self.inner.database.set(database).is_err() {
+            tracing::warn!("Metrics already track a database pool");
+        }
+    }
+
+    /// `route` is the matched route template, e.g. `/todo/:id`, to keep the
+    /// number of series bounded.
+    pub fn record_http_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        let status_class = format!("{}xx", status / 100);
+
+        self.inner
+            .http_requests
+            .with_label_values(&[method, route, &status_class])
+            .inc();
+        self.inner
+            .http_request_duration
+            .with_label_values(&[method, route])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Runs a repository call, recording how long it took and whether it
+    /// failed.
+    pub async fn time_repository<T>(
+        &self,
+        repository: &str,
+        method: &str,
+        call: impl Future<Output = RepositoryResult<T>>,
+    ) -> RepositoryResult<T> {
+        let started_at = Instant::now();
+        let result = call.await;
+        let outcome = if result.is_ok() { "ok" } else { "error" };
+
+        self.inner
+            .repository_call_duration
+            .with_label_values(&[repository, method, outcome])
+            .observe(started_at.elapsed().as_secs_f64());
+
+        result
+    }
+
+    /// All metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        if let Some(database) = self.inner.database.get() {
+            let size = i64::from(database.size());
+            let idle = i64::try_from(database.num_idle()).unwrap_or(i64::MAX);
+
+            let gauges = &self.inner.pool_connections;
+            gauges.with_label_values(&["idle"]).set(idle);
+            gauges.with_label_values(&["in_use"]).set(size - idle);
+        }
+
+        let mut buffer = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&self.inner.registry.gather(), &mut buffer) {
+            tracing::error!("{e}");
+        }
+
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
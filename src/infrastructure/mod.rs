@@ -1,48 +1,261 @@
 use std::{str::FromStr, time::Duration};
 
 use sqlx::{
+    migrate::{Migrate, MigrateError, Migrator},
     postgres::{PgConnectOptions, PgPoolOptions},
-    ConnectOptions, Pool, Postgres,
+    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
+    ConnectOptions, Pool, Postgres, Sqlite,
 };
 
 use crate::error::ServiceStartupError;
 
+pub mod metrics;
 pub mod repositories;
 
+static POSTGRES_MIGRATOR: Migrator = sqlx::migrate!("./migrations/postgres");
+static SQLITE_MIGRATOR: Migrator = sqlx::migrate!("./migrations/sqlite");
+
+/// Connection pool settings, the same for both backends.
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+    pub min_connections: u32,
+    pub max_connections: u32,
+    /// How long a query waits for a free connection before failing.
+    pub acquire_timeout: Duration,
+    /// Connections above `min_connections` idle for this long are closed.
+    pub idle_timeout: Duration,
+    /// Statements taking longer than this are logged as warnings.
+    pub slow_statement_threshold: Duration,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            min_connections: 0,
+            max_connections: 5,
+            acquire_timeout: Duration::from_secs(30),
+            idle_timeout: Duration::from_secs(10 * 60),
+            slow_statement_threshold: Duration::from_millis(500),
+        }
+    }
+}
+
+/// A migration embedded in this build and whether the database has it.
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub applied: bool,
+}
+
+/// A connection pool. The backend is picked from the connection string
+/// scheme, `postgres://` or `sqlite://`.
 #[derive(Clone)]
-pub struct Database {
-    pool: Pool<Postgres>,
+pub enum Database {
+    Postgres(Pool<Postgres>),
+    Sqlite(Pool<Sqlite>),
 }
 
 // Constructor
 impl Database {
-    pub async fn new(connection_string: &str) -> Result<Self, ServiceStartupError> {
+    /// Connects and applies pending migrations.
+    pub async fn new(
+        connection_string: &str,
+        pool_config: &PoolConfig,
+    ) -> Result<Self, ServiceStartupError> {
+        let database = Self::connect(connection_string, pool_config).await?;
+        database.migrate().await?;
+
+        Ok(database)
+    }
+
+    /// Connects without touching the schema.
+    pub async fn connect(
+        connection_string: &str,
+        pool_config: &PoolConfig,
+    ) -> Result<Self, ServiceStartupError> {
+        let scheme = connection_string
+            .split_once(':')
+            .map(|(scheme, _)| scheme)
+            .unwrap_or_default();
+
+        match scheme {
+            "postgres" | "postgresql" => {
+                Self::connect_postgres(connection_string, pool_config).await
+            }
+            "sqlite" => Self::connect_sqlite(connection_string, pool_config).await,
+            _ => {
+                tracing::error!("Unsupported database scheme '{scheme}'");
+                Err(ServiceStartupError::DatabaseConnection)
+            }
+        }
+    }
+
+    async fn connect_postgres(
+        connection_string: &str,
+        pool_config: &PoolConfig,
+    ) -> Result<Self, ServiceStartupError> {
         let mut options = PgConnectOptions::from_str(connection_string).map_err(|e| {
             tracing::error!("{e}");
 
             ServiceStartupError::DatabaseConnection
         })?;
         options.log_statements(tracing::log::LevelFilter::Debug);
-        options.log_slow_statements(tracing::log::LevelFilter::Warn, Duration::from_millis(500));
+        options.log_slow_statements(
+            tracing::log::LevelFilter::Warn,
+            pool_config.slow_statement_threshold,
+        );
 
         let pool = PgPoolOptions::new()
-            .max_connections(5)
+            .min_connections(pool_config.min_connections)
+            .max_connections(pool_config.max_connections)
+            .acquire_timeout(pool_config.acquire_timeout)
+            .idle_timeout(pool_config.idle_timeout)
             .connect_with(options)
             .await
-            .map_err(|_| ServiceStartupError::DatabaseConnection)?;
+            .map_err(|e| {
+                tracing::error!("{e}");
+                ServiceStartupError::DatabaseConnection
+            })?;
 
-        sqlx::migrate!().run(&pool).await.map_err(|e| {
-            tracing::error!("{e}");
-            ServiceStartupError::DatabaseMigration
-        })?;
+        Ok(Self::Postgres(pool))
+    }
+
+    async fn connect_sqlite(
+        connection_string: &str,
+        pool_config: &PoolConfig,
+    ) -> Result<Self, ServiceStartupError> {
+        let mut options = SqliteConnectOptions::from_str(connection_string)
+            .map_err(|e| {
+                tracing::error!("{e}");
 
-        Ok(Self { pool })
+                ServiceStartupError::DatabaseConnection
+            })?
+            .create_if_missing(true)
+            .foreign_keys(true)
+            .journal_mode(SqliteJournalMode::Wal);
+        options.log_statements(tracing::log::LevelFilter::Debug);
+        options.log_slow_statements(
+            tracing::log::LevelFilter::Warn,
+            pool_config.slow_statement_threshold,
+        );
+
+        let pool = SqlitePoolOptions::new()
+            .min_connections(pool_config.min_connections)
+            .max_connections(pool_config.max_connections)
+            .acquire_timeout(pool_config.acquire_timeout)
+            .idle_timeout(pool_config.idle_timeout)
+            .connect_with(options)
+            .await
+            .map_err(|e| {
+                tracing::error!("{e}");
+                ServiceStartupError::DatabaseConnection
+            })?;
+
+        Ok(Self::Sqlite(pool))
     }
 }
 
 // Methods
 impl Database {
-    pub fn pool(&self) -> Pool<Postgres> {
-        self.pool.clone()
+    pub fn backend(&self) -> &'static str {
+        match self {
+            Database::Postgres(_) => "postgres",
+            Database::Sqlite(_) => "sqlite",
+        }
+    }
+
+    /// Migrations embedded in this build for the database's backend.
+    pub fn migrator(&self) -> &'static Migrator {
+        match self {
+            Database::Postgres(_) => &POSTGRES_MIGRATOR,
+            Database::Sqlite(_) => &SQLITE_MIGRATOR,
+        }
+    }
+
+    /// Open connections, idle or in use.
+    pub fn size(&self) -> u32 {
+        match self {
+            Database::Postgres(pool) => pool.size(),
+            Database::Sqlite(pool) => pool.size(),
+        }
+    }
+
+    /// Waits for connections in use to be returned, then closes them all.
+    pub async fn close(&self) {
+        match self {
+            Database::Postgres(pool) => pool.close().await,
+            Database::Sqlite(pool) => pool.close().await,
+        }
+    }
+
+    /// Applies every pending migration.
+    pub async fn migrate(&self) -> Result<(), ServiceStartupError> {
+        let result = match self {
+            Database::Postgres(pool) => POSTGRES_MIGRATOR.run(pool).await,
+            Database::Sqlite(pool) => SQLITE_MIGRATOR.run(pool).await,
+        };
+
+        result.map_err(|e| {
+            tracing::error!("{e}");
+            ServiceStartupError::DatabaseMigration
+        })
+    }
+
+    /// Reverts the applied migrations newer than `target`, `0` reverting
+    /// them all.
+    pub async fn undo(&self, target: i64) -> Result<(), ServiceStartupError> {
+        let result = match self {
+            Database::Postgres(pool) => POSTGRES_MIGRATOR.undo(pool, target).await,
+            Database::Sqlite(pool) => SQLITE_MIGRATOR.undo(pool, target).await,
+        };
+
+        result.map_err(|e| {
+            tracing::error!("{e}");
+            ServiceStartupError::DatabaseMigration
+        })
+    }
+
+    /// Every migration embedded in this build, oldest first.
+    pub async fn migration_status(&self) -> Result<Vec<MigrationStatus>, ServiceStartupError> {
+        let applied = self.applied_versions().await.map_err(|e| {
+            tracing::error!("{e}");
+            ServiceStartupError::DatabaseMigration
+        })?;
+
+        Ok(self
+            .migrator()
+            .iter()
+            .filter(|migration| !migration.migration_type.is_down_migration())
+            .map(|migration| MigrationStatus {
+                version: migration.version,
+                description: migration.description.to_string(),
+                applied: applied.contains(&migration.version),
+            })
+            .collect())
+    }
+
+    async fn applied_versions(&self) -> Result<Vec<i64>, MigrateError> {
+        let applied = match self {
+            Database::Postgres(pool) => {
+                let mut connection = pool.acquire().await?;
+                connection.ensure_migrations_table().await?;
+                connection.list_applied_migrations().await?
+            }
+            Database::Sqlite(pool) => {
+                let mut connection = pool.acquire().await?;
+                connection.ensure_migrations_table().await?;
+                connection.list_applied_migrations().await?
+            }
+        };
+
+        Ok(applied.iter().map(|migration| migration.version).collect())
+    }
+
+    pub fn num_idle(&self) -> usize {
+        match self {
+            Database::Postgres(pool) => pool.num_idle(),
+            Database::Sqlite(pool) => pool.num_idle(),
+        }
     }
 }
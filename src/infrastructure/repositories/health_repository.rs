@@ -0,0 +1,85 @@
+use axum::async_trait;
+
+use crate::{
+    domain::repositories::{
+        error::RepositoryResult,
+        health_repository::{HealthRepositoryPort, StorageHealth},
+    },
+    infrastructure::Database,
+};
+
+use super::map_sqlx_error;
+
+const MIGRATION_VERSION_QUERY: &str = "SELECT MAX(version) FROM _sqlx_migrations WHERE success";
+// The table only exists once migrations ran, which `serve --no-migrate` leaves
+// to a separate job
+const POSTGRES_MIGRATIONS_TABLE_QUERY: &str = "SELECT to_regclass('_sqlx_migrations') IS NOT NULL";
+const SQLITE_MIGRATIONS_TABLE_QUERY: &str =
+    "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations')";
+
+pub struct HealthRepository {
+    database: Database,
+}
+
+impl HealthRepository {
+    pub fn new(database: Database) -> Self {
+        Self { database }
+    }
+}
+
+#[async_trait]
+impl HealthRepositoryPort for HealthRepository {
+    #[tracing::instrument(
+        name = "HealthRepository.check",
+        skip_all,
+        fields(otel.kind = "client", db.system = self.database.backend())
+    )]
+    async fn check(&self) -> RepositoryResult<StorageHealth> {
+        tracing::debug!("HealthRepository.check");
+
+        let migration_version: Option<i64> = match &self.database {
+            Database::Postgres(pool) => {
+                let migrated: bool = sqlx::query_scalar(POSTGRES_MIGRATIONS_TABLE_QUERY)
+                    .fetch_one(pool)
+                    .await
+                    .map_err(map_sqlx_error)?;
+                if migrated {
+                    sqlx::query_scalar(MIGRATION_VERSION_QUERY)
+                        .fetch_one(pool)
+                        .await
+                        .map_err(map_sqlx_error)?
+                } else {
+                    None
+                }
+            }
+            Database::Sqlite(pool) => {
+                let migrated: bool = sqlx::query_scalar(SQLITE_MIGRATIONS_TABLE_QUERY)
+                    .fetch_one(pool)
+                    .await
+                    .map_err(map_sqlx_error)?;
+                if migrated {
+                    sqlx::query_scalar(MIGRATION_VERSION_QUERY)
+                        .fetch_one(pool)
+                        .await
+                        .map_err(map_sqlx_error)?
+                } else {
+                    None
+                }
+            }
+        };
+        let latest_migration = self
+            .database
+            .migrator()
+            .iter()
+            .map(|migration| migration.version)
+            .max();
+
+        Ok(StorageHealth {
+            backend: self.database.backend(),
+            pool_size: self.database.size(),
+            pool_idle: self.database.num_idle(),
+            migration_version,
+            migrations_pending: latest_migration > migration_version,
+        })
+    }
+}
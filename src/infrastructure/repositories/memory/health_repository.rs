@@ -0,0 +1,36 @@
+use axum::async_trait;
+
+use crate::domain::repositories::{
+    error::RepositoryResult,
+    health_repository::{HealthRepositoryPort, StorageHealth},
+};
+
+use super::Store;
+
+pub struct InMemoryHealthRepository {
+    store: Store,
+}
+
+impl InMemoryHealthRepository {
+    pub fn new(store: Store) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl HealthRepositoryPort for InMemoryHealthRepository {
+    async fn check(&self) -> RepositoryResult<StorageHealth> {
+        tracing::debug!("InMemoryHealthRepository.check");
+
+        // Fails if a panic poisoned the store
+        let _tables = self.store.read()?;
+
+        Ok(StorageHealth {
+            backend: "memory",
+            pool_size: 0,
+            pool_idle: 0,
+            migration_version: None,
+            migrations_pending: false,
+        })
+    }
+}
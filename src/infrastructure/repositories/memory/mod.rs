@@ -0,0 +1,105 @@
+//! Repositories that keep everything in process memory. They mirror the
+//! Postgres repositories' semantics (soft deletes, uniqueness, not-found
+//! handling) so the API can run without a database, but nothing survives a
+//! restart.
+
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use sqlx::types::{time::OffsetDateTime, Uuid};
+
+use crate::domain::{
+    entities::{todo::Priority, token::Scope, user::Role},
+    repositories::error::{RepositoryError, RepositoryResult},
+};
+
+pub mod health_repository;
+pub mod session_repository;
+pub mod todo_repository;
+pub mod token_repository;
+pub mod user_repository;
+
+#[derive(Clone)]
+struct TodoRecord {
+    id: Uuid,
+    owner_id: Uuid,
+    title: String,
+    description: String,
+    priority: Priority,
+    due_at: Option<OffsetDateTime>,
+    completed_at: Option<OffsetDateTime>,
+    deleted_at: Option<OffsetDateTime>,
+    version: i64,
+    created_at: OffsetDateTime,
+    updated_at: OffsetDateTime,
+}
+
+#[derive(Clone)]
+struct UserRecord {
+    id: Uuid,
+    email: String,
+    first_name: String,
+    password_hash: Option<String>,
+    role: Role,
+    deleted_at: Option<OffsetDateTime>,
+    version: i64,
+    created_at: OffsetDateTime,
+    updated_at: OffsetDateTime,
+}
+
+#[derive(Clone)]
+struct SessionRecord {
+    id: Uuid,
+    user_id: Uuid,
+    expires_at: OffsetDateTime,
+}
+
+#[derive(Clone)]
+struct TokenRecord {
+    id: Uuid,
+    user_id: Uuid,
+    name: String,
+    token_hash: String,
+    scopes: Vec<Scope>,
+    expires_at: Option<OffsetDateTime>,
+    last_used_at: Option<OffsetDateTime>,
+    created_at: OffsetDateTime,
+}
+
+#[derive(Default)]
+struct Tables {
+    todos: HashMap<Uuid, TodoRecord>,
+    users: HashMap<Uuid, UserRecord>,
+    sessions: HashMap<Uuid, SessionRecord>,
+    tokens: HashMap<Uuid, TokenRecord>,
+}
+
+/// Shared by all in-memory repositories so that removing a user cascades to
+/// their todos, sessions and tokens, like the foreign keys do in Postgres.
+#[derive(Clone, Default)]
+pub struct Store {
+    tables: Arc<RwLock<Tables>>,
+}
+
+impl Store {
+    fn read(&self) -> RepositoryResult<RwLockReadGuard<'_, Tables>> {
+        self.tables.read().map_err(|e| {
+            tracing::error!("{e}");
+            RepositoryError::Unknown
+        })
+    }
+
+    fn write(&self) -> RepositoryResult<RwLockWriteGuard<'_, Tables>> {
+        self.tables.write().map_err(|e| {
+            tracing::error!("{e}");
+            RepositoryError::Unknown
+        })
+    }
+}
+
+fn parse_uuid(id: &str) -> RepositoryResult<Uuid> {
+    Uuid::from_str(id).map_err(|_| RepositoryError::InvalidUuid)
+}
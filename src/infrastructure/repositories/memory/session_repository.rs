@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+
+use crate::domain::{
+    entities::session::Session,
+    id_generator::IdGenerator,
+    repositories::{
+        error::{RepositoryError, RepositoryResult},
+        session_repository::{CreateInput, SessionRepositoryPort},
+    },
+};
+
+use super::{parse_uuid, SessionRecord, Store};
+
+impl From<SessionRecord> for Session {
+    fn from(val: SessionRecord) -> Self {
+        Session {
+            id: val.id.into(),
+            user_id: val.user_id.into(),
+            expires_at: val.expires_at,
+        }
+    }
+}
+
+pub struct InMemorySessionRepository {
+    store: Store,
+    ids: Arc<dyn IdGenerator>,
+}
+
+impl InMemorySessionRepository {
+    pub fn new(store: Store, ids: Arc<dyn IdGenerator>) -> Self {
+        Self { store, ids }
+    }
+}
+
+#[async_trait]
+impl SessionRepositoryPort for InMemorySessionRepository {
+    async fn find_by_id(&self, id: String) -> RepositoryResult<Session> {
+        tracing::debug!("InMemorySessionRepository.find_by_id");
+
+        let id = parse_uuid(&id)?;
+
+        self.store
+            .read()?
+            .sessions
+            .get(&id)
+            .cloned()
+            .map(Session::from)
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn create(&self, input: CreateInput) -> RepositoryResult<Session> {
+        tracing::debug!("InMemorySessionRepository.create | {}", input.user_id);
+
+        let user_id = input.user_id.as_uuid();
+
+        let mut tables = self.store.write()?;
+        if !tables.users.contains_key(&user_id) {
+            tracing::error!("Session references unknown user {user_id}");
+            return Err(RepositoryError::Unknown);
+        }
+
+        let session = SessionRecord {
+            id: self.ids.generate(),
+            user_id,
+            expires_at: input.expires_at,
+        };
+        tables.sessions.insert(session.id, session.clone());
+
+        Ok(session.into())
+    }
+
+    async fn delete(&self, id: String) -> RepositoryResult<()> {
+        tracing::debug!("InMemorySessionRepository.delete");
+
+        let id = parse_uuid(&id)?;
+        self.store.write()?.sessions.remove(&id);
+
+        Ok(())
+    }
+}
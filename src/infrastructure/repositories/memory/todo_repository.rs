@@ -0,0 +1,335 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    sync::Arc,
+};
+
+use axum::async_trait;
+use sqlx::types::{time::OffsetDateTime, Uuid};
+
+use crate::domain::{
+    clock::Clock,
+    entities::todo::Todo,
+    id_generator::IdGenerator,
+    ids::{TodoId, UserId},
+    repositories::{
+        error::{RepositoryError, RepositoryResult},
+        pagination::{CursorValue, SortDirection},
+        todo_repository::{
+            CreateInput, ListQuery, TodoCursor, TodoPage, TodoRepositoryPort, TodoSortField,
+            UpdateInput,
+        },
+    },
+};
+
+use super::{parse_uuid, Store, TodoRecord};
+
+impl From<TodoRecord> for Todo {
+    fn from(val: TodoRecord) -> Self {
+        Todo {
+            id: val.id.into(),
+            owner_id: val.owner_id.into(),
+            title: val.title,
+            description: val.description,
+            priority: val.priority,
+            due_at: val.due_at,
+            completed_at: val.completed_at,
+            deleted_at: val.deleted_at,
+            version: val.version,
+            created_at: val.created_at,
+            updated_at: val.updated_at,
+        }
+    }
+}
+
+impl TodoRecord {
+    fn sort_value(&self, sort_by: TodoSortField) -> CursorValue {
+        match sort_by {
+            TodoSortField::CreatedAt => CursorValue::Timestamp(self.created_at),
+            TodoSortField::UpdatedAt => CursorValue::Timestamp(self.updated_at),
+            TodoSortField::Title => CursorValue::Text(self.title.clone()),
+        }
+    }
+
+    fn matches(&self, query: &ListQuery, owner_id: Uuid) -> bool {
+        let in_range = |value: OffsetDateTime,
+                        after: Option<OffsetDateTime>,
+                        before: Option<OffsetDateTime>| {
+            after.is_none_or(|after| value >= after) && before.is_none_or(|before| value < before)
+        };
+
+        self.owner_id == owner_id
+            && self.deleted_at.is_none()
+            && query
+                .completed
+                .is_none_or(|completed| self.completed_at.is_some() == completed)
+            && query.search.as_ref().is_none_or(|search| {
+                let search = search.to_lowercase();
+                self.title.to_lowercase().contains(&search)
+                    || self.description.to_lowercase().contains(&search)
+            })
+            && in_range(self.created_at, query.created_after, query.created_before)
+            && in_range(self.updated_at, query.updated_after, query.updated_before)
+    }
+}
+
+fn compare_values(left: &CursorValue, right: &CursorValue) -> Ordering {
+    match (left, right) {
+        (CursorValue::Timestamp(left), CursorValue::Timestamp(right)) => left.cmp(right),
+        (CursorValue::Text(left), CursorValue::Text(right)) => left.cmp(right),
+        (CursorValue::Timestamp(_), CursorValue::Text(_)) => Ordering::Less,
+        (CursorValue::Text(_), CursorValue::Timestamp(_)) => Ordering::Greater,
+    }
+}
+
+pub struct InMemoryTodoRepository {
+    store: Store,
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
+}
+
+impl InMemoryTodoRepository {
+    pub fn new(store: Store, clock: Arc<dyn Clock>, ids: Arc<dyn IdGenerator>) -> Self {
+        Self { store, clock, ids }
+    }
+}
+
+#[async_trait]
+impl TodoRepositoryPort for InMemoryTodoRepository {
+    async fn list(&self, query: ListQuery) -> RepositoryResult<TodoPage> {
+        tracing::debug!("InMemoryTodoRepository.list | {query:?}");
+
+        let owner_id = query.owner_id.as_uuid();
+        let after = match &query.after {
+            Some(cursor) => Some((cursor.value.clone(), parse_uuid(&cursor.id)?)),
+            None => None,
+        };
+
+        let compare = |left: &(CursorValue, Uuid), right: &(CursorValue, Uuid)| {
+            let ordering = compare_values(&left.0, &right.0).then(left.1.cmp(&right.1));
+            match query.direction {
+                SortDirection::Asc => ordering,
+                SortDirection::Desc => ordering.reverse(),
+            }
+        };
+
+        let mut records: Vec<((CursorValue, Uuid), TodoRecord)> = self
+            .store
+            .read()?
+            .todos
+            .values()
+            .filter(|todo| todo.matches(&query, owner_id))
+            .map(|todo| ((todo.sort_value(query.sort_by), todo.id), todo.clone()))
+            .filter(|(key, _)| {
+                after
+                    .as_ref()
+                    .is_none_or(|after| compare(key, after) == Ordering::Greater)
+            })
+            .collect();
+        records.sort_by(|(left, _), (right, _)| compare(left, right));
+
+        let limit = query.limit as usize;
+        let next_cursor = if records.len() > limit {
+            records.truncate(limit);
+            records.last().map(|((value, id), _)| TodoCursor {
+                sort_by: query.sort_by,
+                direction: query.direction,
+                value: value.clone(),
+                id: id.to_string(),
+            })
+        } else {
+            None
+        };
+
+        Ok(TodoPage {
+            items: records.into_iter().map(|(_, todo)| todo.into()).collect(),
+            next_cursor,
+        })
+    }
+
+    async fn find_by_id(&self, id: TodoId) -> RepositoryResult<Todo> {
+        tracing::debug!("InMemoryTodoRepository.find_by_id | {id}");
+
+        let id = id.as_uuid();
+
+        self.store
+            .read()?
+            .todos
+            .get(&id)
+            .filter(|todo| todo.deleted_at.is_none())
+            .cloned()
+            .map(Todo::from)
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn find_by_owner_and_id(&self, owner_id: UserId, id: TodoId) -> RepositoryResult<Todo> {
+        tracing::debug!("InMemoryTodoRepository.find_by_owner_and_id | {owner_id} | {id}");
+
+        let id = id.as_uuid();
+        let owner_id = owner_id.as_uuid();
+
+        self.store
+            .read()?
+            .todos
+            .get(&id)
+            .filter(|todo| todo.deleted_at.is_none() && todo.owner_id == owner_id)
+            .cloned()
+            .map(Todo::from)
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn update_one(&self, input: UpdateInput) -> RepositoryResult<Todo> {
+        tracing::debug!("InMemoryTodoRepository.update_one | {input:?}");
+
+        let id = input.id.as_uuid();
+
+        let mut tables = self.store.write()?;
+        let todo = tables
+            .todos
+            .get_mut(&id)
+            .filter(|todo| todo.deleted_at.is_none())
+            .ok_or(RepositoryError::NotFound)?;
+
+        if input.version.is_some_and(|version| version != todo.version) {
+            return Err(RepositoryError::VersionMismatch);
+        }
+
+        if let Some(title) = input.title {
+            todo.title = title.into_inner();
+        }
+        if let Some(description) = input.description {
+            todo.description = description;
+        }
+        if let Some(priority) = input.priority {
+            todo.priority = priority;
+        }
+        if let Some(due_at) = input.due_at {
+            todo.due_at = due_at;
+        }
+        if let Some(completed_at) = input.completed_at {
+            todo.completed_at = completed_at;
+        }
+        todo.updated_at = self.clock.now();
+        todo.version += 1;
+
+        Ok(todo.clone().into())
+    }
+
+    async fn create(&self, input: CreateInput) -> RepositoryResult<Todo> {
+        tracing::debug!("InMemoryTodoRepository.create | {input:?}");
+
+        let owner_id = input.owner_id.as_uuid();
+
+        let mut tables = self.store.write()?;
+        if !tables.users.contains_key(&owner_id) {
+            tracing::error!("Todo references unknown owner {owner_id}");
+            return Err(RepositoryError::Unknown);
+        }
+
+        let now = self.clock.now();
+        let todo = TodoRecord {
+            id: self.ids.generate(),
+            owner_id,
+            title: input.title.into_inner(),
+            description: input.description,
+            priority: input.priority,
+            due_at: input.due_at,
+            completed_at: None,
+            deleted_at: None,
+            version: 1,
+            created_at: now,
+            updated_at: now,
+        };
+        tables.todos.insert(todo.id, todo.clone());
+
+        Ok(todo.into())
+    }
+
+    async fn delete(&self, id: TodoId) -> RepositoryResult<()> {
+        tracing::debug!("InMemoryTodoRepository.delete | {id}");
+
+        let id = id.as_uuid();
+
+        let mut tables = self.store.write()?;
+        let todo = tables
+            .todos
+            .get_mut(&id)
+            .filter(|todo| todo.deleted_at.is_none())
+            .ok_or(RepositoryError::NotFound)?;
+
+        let now = self.clock.now();
+        todo.deleted_at = Some(now);
+        todo.updated_at = now;
+        todo.version += 1;
+
+        Ok(())
+    }
+
+    async fn list_deleted_by_owner(&self, owner_id: UserId) -> RepositoryResult<Vec<Todo>> {
+        tracing::debug!("InMemoryTodoRepository.list_deleted_by_owner | {owner_id}");
+
+        let owner_id = owner_id.as_uuid();
+
+        let mut todos: Vec<TodoRecord> = self
+            .store
+            .read()?
+            .todos
+            .values()
+            .filter(|todo| todo.owner_id == owner_id && todo.deleted_at.is_some())
+            .cloned()
+            .collect();
+        todos.sort_by_key(|todo| Reverse(todo.deleted_at));
+
+        Ok(todos.into_iter().map(|todo| todo.into()).collect())
+    }
+
+    async fn find_deleted_by_owner_and_id(
+        &self,
+        owner_id: UserId,
+        id: TodoId,
+    ) -> RepositoryResult<Todo> {
+        tracing::debug!("InMemoryTodoRepository.find_deleted_by_owner_and_id | {owner_id} | {id}");
+
+        let id = id.as_uuid();
+        let owner_id = owner_id.as_uuid();
+
+        self.store
+            .read()?
+            .todos
+            .get(&id)
+            .filter(|todo| todo.deleted_at.is_some() && todo.owner_id == owner_id)
+            .cloned()
+            .map(Todo::from)
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn restore(&self, id: TodoId) -> RepositoryResult<Todo> {
+        tracing::debug!("InMemoryTodoRepository.restore | {id}");
+
+        let id = id.as_uuid();
+
+        let mut tables = self.store.write()?;
+        let todo = tables
+            .todos
+            .get_mut(&id)
+            .filter(|todo| todo.deleted_at.is_some())
+            .ok_or(RepositoryError::NotFound)?;
+
+        todo.deleted_at = None;
+        todo.updated_at = self.clock.now();
+        todo.version += 1;
+
+        Ok(todo.clone().into())
+    }
+
+    async fn purge_deleted(&self, deleted_before: OffsetDateTime) -> RepositoryResult<u64> {
+        tracing::debug!("InMemoryTodoRepository.purge_deleted | {deleted_before}");
+
+        let mut tables = self.store.write()?;
+        let before = tables.todos.len();
+        tables.todos.retain(
+            |_, todo| !matches!(todo.deleted_at, Some(deleted_at) if deleted_at < deleted_before),
+        );
+
+        Ok((before - tables.todos.len()) as u64)
+    }
+}
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use sqlx::types::time::OffsetDateTime;
+
+use crate::domain::{
+    clock::Clock,
+    entities::token::Token,
+    id_generator::IdGenerator,
+    ids::{TokenId, UserId},
+    repositories::{
+        error::{RepositoryError, RepositoryResult},
+        token_repository::{CreateInput, TokenRepositoryPort},
+    },
+};
+
+use super::{Store, TokenRecord};
+
+impl From<TokenRecord> for Token {
+    fn from(val: TokenRecord) -> Self {
+        Token {
+            id: val.id.into(),
+            user_id: val.user_id.into(),
+            name: val.name,
+            scopes: val.scopes,
+            expires_at: val.expires_at,
+            last_used_at: val.last_used_at,
+            created_at: val.created_at,
+        }
+    }
+}
+
+pub struct InMemoryTokenRepository {
+    store: Store,
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
+}
+
+impl InMemoryTokenRepository {
+    pub fn new(store: Store, clock: Arc<dyn Clock>, ids: Arc<dyn IdGenerator>) -> Self {
+        Self { store, clock, ids }
+    }
+}
+
+#[async_trait]
+impl TokenRepositoryPort for InMemoryTokenRepository {
+    async fn find_by_hash(&self, token_hash: String) -> RepositoryResult<Token> {
+        tracing::debug!("InMemoryTokenRepository.find_by_hash");
+
+        self.store
+            .read()?
+            .tokens
+            .values()
+            .find(|token| token.token_hash == token_hash)
+            .cloned()
+            .map(Token::from)
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn list_by_user(&self, user_id: UserId) -> RepositoryResult<Vec<Token>> {
+        tracing::debug!("InMemoryTokenRepository.list_by_user | {user_id}");
+
+        let user_id = user_id.as_uuid();
+
+        let mut tokens: Vec<TokenRecord> = self
+            .store
+            .read()?
+            .tokens
+            .values()
+            .filter(|token| token.user_id == user_id)
+            .cloned()
+            .collect();
+        tokens.sort_by_key(|token| (token.created_at, token.id));
+
+        Ok(tokens.into_iter().map(Token::from).collect())
+    }
+
+    async fn create(&self, input: CreateInput) -> RepositoryResult<Token> {
+        tracing::debug!("InMemoryTokenRepository.create | {}", input.user_id);
+
+        let user_id = input.user_id.as_uuid();
+
+        let mut tables = self.store.write()?;
+        if !tables.users.contains_key(&user_id) {
+            tracing::error!("Token references unknown user {user_id}");
+            return Err(RepositoryError::Unknown);
+        }
+
+        let token = TokenRecord {
+            id: self.ids.generate(),
+            user_id,
+            name: input.name,
+            token_hash: input.token_hash,
+            scopes: input.scopes,
+            expires_at: input.expires_at,
+            last_used_at: None,
+            created_at: self.clock.now(),
+        };
+        tables.tokens.insert(token.id, token.clone());
+
+        Ok(token.into())
+    }
+
+    async fn touch(&self, id: TokenId, used_at: OffsetDateTime) -> RepositoryResult<()> {
+        tracing::debug!("InMemoryTokenRepository.touch | {id}");
+
+        let id = id.as_uuid();
+        if let Some(token) = self.store.write()?.tokens.get_mut(&id) {
+            token.last_used_at = Some(used_at);
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: UserId, id: TokenId) -> RepositoryResult<()> {
+        tracing::debug!("InMemoryTokenRepository.delete | {id}");
+
+        let user_id = user_id.as_uuid();
+        let id = id.as_uuid();
+
+        let mut tables = self.store.write()?;
+        match tables.tokens.get(&id) {
+            Some(token) if token.user_id == user_id => {
+                tables.tokens.remove(&id);
+                Ok(())
+            }
+            _ => Err(RepositoryError::NotFound),
+        }
+    }
+}
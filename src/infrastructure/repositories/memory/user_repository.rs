@@ -0,0 +1,243 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use sqlx::types::{time::OffsetDateTime, Uuid};
+
+use crate::domain::{
+    clock::Clock,
+    entities::user::{Role, User},
+    id_generator::IdGenerator,
+    ids::UserId,
+    repositories::{
+        error::{RepositoryError, RepositoryResult},
+        user_repository::{CreateInput, UpdateInput, UserRepositoryPort},
+    },
+};
+
+use super::{Store, Tables, UserRecord};
+
+impl From<UserRecord> for User {
+    fn from(val: UserRecord) -> Self {
+        User {
+            id: val.id.into(),
+            email: val.email,
+            first_name: val.first_name,
+            password_hash: val.password_hash,
+            role: val.role,
+            version: val.version,
+            created_at: val.created_at,
+            updated_at: val.updated_at,
+        }
+    }
+}
+
+pub struct InMemoryUserRepository {
+    store: Store,
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
+}
+
+impl InMemoryUserRepository {
+    pub fn new(store: Store, clock: Arc<dyn Clock>, ids: Arc<dyn IdGenerator>) -> Self {
+        Self { store, clock, ids }
+    }
+}
+
+impl Tables {
+    /// Emails are unique among users not deleted, ignoring case, like the
+    /// `users_email_key` index.
+    fn ensure_email_free(&self, email: &str, except: Option<Uuid>) -> RepositoryResult<()> {
+        let taken = self.users.values().any(|user| {
+            user.deleted_at.is_none()
+                && user.email.to_lowercase() == email.to_lowercase()
+                && Some(user.id) != except
+        });
+        if taken {
+            return Err(RepositoryError::Conflict {
+                field: "email".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UserRepositoryPort for InMemoryUserRepository {
+    async fn find_by_id(&self, id: UserId) -> RepositoryResult<User> {
+        tracing::debug!("InMemoryUserRepository.find_by_id | {id}");
+
+        let id = id.as_uuid();
+
+        self.store
+            .read()?
+            .users
+            .get(&id)
+            .filter(|user| user.deleted_at.is_none())
+            .cloned()
+            .map(User::from)
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn find_by_email(&self, email: String) -> RepositoryResult<Option<User>> {
+        tracing::debug!("InMemoryUserRepository.find_by_email | {email}");
+
+        Ok(self
+            .store
+            .read()?
+            .users
+            .values()
+            .find(|user| {
+                user.deleted_at.is_none() && user.email.to_lowercase() == email.to_lowercase()
+            })
+            .cloned()
+            .map(User::from))
+    }
+
+    async fn list(&self, search: Option<String>) -> RepositoryResult<Vec<User>> {
+        tracing::debug!("InMemoryUserRepository.list | {search:?}");
+
+        let search = search.map(|search| search.to_lowercase());
+        let mut users: Vec<User> = self
+            .store
+            .read()?
+            .users
+            .values()
+            .filter(|user| user.deleted_at.is_none())
+            .filter(|user| {
+                search.as_ref().is_none_or(|search| {
+                    user.email.to_lowercase().contains(search)
+                        || user.first_name.to_lowercase().contains(search)
+                })
+            })
+            .cloned()
+            .map(User::from)
+            .collect();
+        users.sort_by(|a, b| a.email.cmp(&b.email));
+
+        Ok(users)
+    }
+
+    async fn update_one(&self, id: UserId, input: UpdateInput) -> RepositoryResult<User> {
+        tracing::debug!("InMemoryUserRepository.update_one | {id} | {input:?}");
+
+        let id = id.as_uuid();
+        let mut tables = self.store.write()?;
+
+        if let Some(email) = &input.email {
+            tables.ensure_email_free(email.as_str(), Some(id))?;
+        }
+
+        let user = tables
+            .users
+            .get_mut(&id)
+            .filter(|user| user.deleted_at.is_none())
+            .ok_or(RepositoryError::NotFound)?;
+
+        if input.version.is_some_and(|version| version != user.version) {
+            return Err(RepositoryError::VersionMismatch);
+        }
+
+        if let Some(email) = input.email {
+            user.email = email.into_inner();
+        }
+        if let Some(first_name) = input.first_name {
+            user.first_name = first_name.into_inner();
+        }
+        user.updated_at = self.clock.now();
+        user.version += 1;
+
+        Ok(user.clone().into())
+    }
+
+    async fn create(&self, input: CreateInput) -> RepositoryResult<User> {
+        tracing::debug!("InMemoryUserRepository.create | {input:?}");
+
+        let mut tables = self.store.write()?;
+
+        tables.ensure_email_free(input.email.as_str(), None)?;
+
+        let now = self.clock.now();
+        let user = UserRecord {
+            id: self.ids.generate(),
+            email: input.email.into_inner(),
+            first_name: input.first_name.into_inner(),
+            password_hash: Some(input.password_hash),
+            role: input.role,
+            deleted_at: None,
+            version: 1,
+            created_at: now,
+            updated_at: now,
+        };
+        tables.users.insert(user.id, user.clone());
+
+        Ok(user.into())
+    }
+
+    async fn set_role(&self, id: UserId, role: Role) -> RepositoryResult<User> {
+        tracing::debug!("InMemoryUserRepository.set_role | {id} | {role:?}");
+
+        let id = id.as_uuid();
+        let mut tables = self.store.write()?;
+        let user = tables
+            .users
+            .get_mut(&id)
+            .filter(|user| user.deleted_at.is_none())
+            .ok_or(RepositoryError::NotFound)?;
+
+        user.role = role;
+        user.updated_at = self.clock.now();
+        user.version += 1;
+
+        Ok(user.clone().into())
+    }
+
+    async fn delete(&self, id: UserId) -> RepositoryResult<()> {
+        tracing::debug!("InMemoryUserRepository.delete | {id}");
+
+        let id = id.as_uuid();
+
+        let mut tables = self.store.write()?;
+        let user = tables
+            .users
+            .get_mut(&id)
+            .filter(|user| user.deleted_at.is_none())
+            .ok_or(RepositoryError::NotFound)?;
+
+        let now = self.clock.now();
+        user.deleted_at = Some(now);
+        user.updated_at = now;
+        user.version += 1;
+
+        Ok(())
+    }
+
+    async fn purge_deleted(&self, deleted_before: OffsetDateTime) -> RepositoryResult<u64> {
+        tracing::debug!("InMemoryUserRepository.purge_deleted | {deleted_before}");
+
+        let mut tables = self.store.write()?;
+        let purged: Vec<Uuid> = tables
+            .users
+            .values()
+            .filter(
+                |user| matches!(user.deleted_at, Some(deleted_at) if deleted_at < deleted_before),
+            )
+            .map(|user| user.id)
+            .collect();
+
+        for id in &purged {
+            tables.users.remove(id);
+        }
+        tables
+            .todos
+            .retain(|_, todo| !purged.contains(&todo.owner_id));
+        tables
+            .sessions
+            .retain(|_, session| !purged.contains(&session.user_id));
+        tables
+            .tokens
+            .retain(|_, token| !purged.contains(&token.user_id));
+
+        Ok(purged.len() as u64)
+    }
+}
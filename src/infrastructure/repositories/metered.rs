@@ -0,0 +1,267 @@
+//! Decorators recording how long each repository method takes, whatever the
+//! backend.
+
+use std::sync::Arc;
+
+use axum::async_trait;
+use time::OffsetDateTime;
+
+use crate::{
+    domain::{
+        entities::{
+            session::Session,
+            todo::Todo,
+            token::Token,
+            user::{Role, User},
+        },
+        ids::{TodoId, TokenId, UserId},
+        repositories::{
+            error::RepositoryResult,
+            session_repository::{self, SessionRepositoryPort},
+            todo_repository::{self, ListQuery, TodoPage, TodoRepositoryPort},
+            token_repository::{self, TokenRepositoryPort},
+            user_repository::{self, UserRepositoryPort},
+        },
+    },
+    infrastructure::metrics::Metrics,
+};
+
+pub struct MeteredTodoRepository {
+    inner: Arc<dyn TodoRepositoryPort>,
+    metrics: Metrics,
+}
+
+impl MeteredTodoRepository {
+    pub fn new(inner: Arc<dyn TodoRepositoryPort>, metrics: Metrics) -> Self {
+        Self { inner, metrics }
+    }
+}
+
+#[async_trait]
+impl TodoRepositoryPort for MeteredTodoRepository {
+    async fn list(&self, query: ListQuery) -> RepositoryResult<TodoPage> {
+        self.metrics
+            .time_repository("todo", "list", self.inner.list(query))
+            .await
+    }
+
+    async fn find_by_id(&self, id: TodoId) -> RepositoryResult<Todo> {
+        self.metrics
+            .time_repository("todo", "find_by_id", self.inner.find_by_id(id))
+            .await
+    }
+
+    async fn find_by_owner_and_id(&self, owner_id: UserId, id: TodoId) -> RepositoryResult<Todo> {
+        self.metrics
+            .time_repository(
+                "todo",
+                "find_by_owner_and_id",
+                self.inner.find_by_owner_and_id(owner_id, id),
+            )
+            .await
+    }
+
+    async fn update_one(&self, input: todo_repository::UpdateInput) -> RepositoryResult<Todo> {
+        self.metrics
+            .time_repository("todo", "update_one", self.inner.update_one(input))
+            .await
+    }
+
+    async fn create(&self, input: todo_repository::CreateInput) -> RepositoryResult<Todo> {
+        self.metrics
+            .time_repository("todo", "create", self.inner.create(input))
+            .await
+    }
+
+    async fn delete(&self, id: TodoId) -> RepositoryResult<()> {
+        self.metrics
+            .time_repository("todo", "delete", self.inner.delete(id))
+            .await
+    }
+
+    async fn list_deleted_by_owner(&self, owner_id: UserId) -> RepositoryResult<Vec<Todo>> {
+        self.metrics
+            .time_repository(
+                "todo",
+                "list_deleted_by_owner",
+                self.inner.list_deleted_by_owner(owner_id),
+            )
+            .await
+    }
+
+    async fn find_deleted_by_owner_and_id(
+        &self,
+        owner_id: UserId,
+        id: TodoId,
+    ) -> RepositoryResult<Todo> {
+        self.metrics
+            .time_repository(
+                "todo",
+                "find_deleted_by_owner_and_id",
+                self.inner.find_deleted_by_owner_and_id(owner_id, id),
+            )
+            .await
+    }
+
+    async fn restore(&self, id: TodoId) -> RepositoryResult<Todo> {
+        self.metrics
+            .time_repository("todo", "restore", self.inner.restore(id))
+            .await
+    }
+
+    async fn purge_deleted(&self, deleted_before: OffsetDateTime) -> RepositoryResult<u64> {
+        self.metrics
+            .time_repository(
+                "todo",
+                "purge_deleted",
+                self.inner.purge_deleted(deleted_before),
+            )
+            .await
+    }
+}
+
+pub struct MeteredUserRepository {
+    inner: Arc<dyn UserRepositoryPort>,
+    metrics: Metrics,
+}
+
+impl MeteredUserRepository {
+    pub fn new(inner: Arc<dyn UserRepositoryPort>, metrics: Metrics) -> Self {
+        Self { inner, metrics }
+    }
+}
+
+#[async_trait]
+impl UserRepositoryPort for MeteredUserRepository {
+    async fn find_by_id(&self, id: UserId) -> RepositoryResult<User> {
+        self.metrics
+            .time_repository("user", "find_by_id", self.inner.find_by_id(id))
+            .await
+    }
+
+    async fn find_by_email(&self, email: String) -> RepositoryResult<Option<User>> {
+        self.metrics
+            .time_repository("user", "find_by_email", self.inner.find_by_email(email))
+            .await
+    }
+
+    async fn list(&self, search: Option<String>) -> RepositoryResult<Vec<User>> {
+        self.metrics
+            .time_repository("user", "list", self.inner.list(search))
+            .await
+    }
+
+    async fn update_one(
+        &self,
+        id: UserId,
+        input: user_repository::UpdateInput,
+    ) -> RepositoryResult<User> {
+        self.metrics
+            .time_repository("user", "update_one", self.inner.update_one(id, input))
+            .await
+    }
+
+    async fn create(&self, input: user_repository::CreateInput) -> RepositoryResult<User> {
+        self.metrics
+            .time_repository("user", "create", self.inner.create(input))
+            .await
+    }
+
+    async fn set_role(&self, id: UserId, role: Role) -> RepositoryResult<User> {
+        self.metrics
+            .time_repository("user", "set_role", self.inner.set_role(id, role))
+            .await
+    }
+
+    async fn delete(&self, id: UserId) -> RepositoryResult<()> {
+        self.metrics
+            .time_repository("user", "delete", self.inner.delete(id))
+            .await
+    }
+
+    async fn purge_deleted(&self, deleted_before: OffsetDateTime) -> RepositoryResult<u64> {
+        self.metrics
+            .time_repository(
+                "user",
+                "purge_deleted",
+                self.inner.purge_deleted(deleted_before),
+            )
+            .await
+    }
+}
+
+pub struct MeteredSessionRepository {
+    inner: Arc<dyn SessionRepositoryPort>,
+    metrics: Metrics,
+}
+
+impl MeteredSessionRepository {
+    pub fn new(inner: Arc<dyn SessionRepositoryPort>, metrics: Metrics) -> Self {
+        Self { inner, metrics }
+    }
+}
+
+#[async_trait]
+impl SessionRepositoryPort for MeteredSessionRepository {
+    async fn find_by_id(&self, id: String) -> RepositoryResult<Session> {
+        self.metrics
+            .time_repository("session", "find_by_id", self.inner.find_by_id(id))
+            .await
+    }
+
+    async fn create(&self, input: session_repository::CreateInput) -> RepositoryResult<Session> {
+        self.metrics
+            .time_repository("session", "create", self.inner.create(input))
+            .await
+    }
+
+    async fn delete(&self, id: String) -> RepositoryResult<()> {
+        self.metrics
+            .time_repository("session", "delete", self.inner.delete(id))
+            .await
+    }
+}
+
+pub struct MeteredTokenRepository {
+    inner: Arc<dyn TokenRepositoryPort>,
+    metrics: Metrics,
+}
+
+impl MeteredTokenRepository {
+    pub fn new(inner: Arc<dyn TokenRepositoryPort>, metrics: Metrics) -> Self {
+        Self { inner, metrics }
+    }
+}
+
+#[async_trait]
+impl TokenRepositoryPort for MeteredTokenRepository {
+    async fn find_by_hash(&self, token_hash: String) -> RepositoryResult<Token> {
+        self.metrics
+            .time_repository("token", "find_by_hash", self.inner.find_by_hash(token_hash))
+            .await
+    }
+
+    async fn list_by_user(&self, user_id: UserId) -> RepositoryResult<Vec<Token>> {
+        self.metrics
+            .time_repository("token", "list_by_user", self.inner.list_by_user(user_id))
+            .await
+    }
+
+    async fn create(&self, input: token_repository::CreateInput) -> RepositoryResult<Token> {
+        self.metrics
+            .time_repository("token", "create", self.inner.create(input))
+            .await
+    }
+
+    async fn touch(&self, id: TokenId, used_at: OffsetDateTime) -> RepositoryResult<()> {
+        self.metrics
+            .time_repository("token", "touch", self.inner.touch(id, used_at))
+            .await
+    }
+
+    async fn delete(&self, user_id: UserId, id: TokenId) -> RepositoryResult<()> {
+        self.metrics
+            .time_repository("token", "delete", self.inner.delete(user_id, id))
+            .await
+    }
+}
@@ -1,2 +1,110 @@
+use std::str::FromStr;
+
+use sqlx::{
+    error::DatabaseError,
+    types::time::{OffsetDateTime, PrimitiveDateTime, UtcOffset},
+    Error,
+};
+
+use crate::domain::{entities::token::Scope, repositories::error::RepositoryError};
+
+pub mod health_repository;
+pub mod memory;
+pub mod metered;
+pub mod session_repository;
+pub mod sqlite;
 pub mod todo_repository;
+pub mod token_repository;
 pub mod user_repository;
+
+/// Postgres' `unique_violation` and SQLite's `SQLITE_CONSTRAINT_UNIQUE` and
+/// `SQLITE_CONSTRAINT_PRIMARYKEY`.
+const UNIQUE_VIOLATION_CODES: [&str; 3] = ["23505", "2067", "1555"];
+
+/// Logs a sqlx error and maps it onto the matching [`RepositoryError`]. A
+/// missing row is expected and not logged.
+fn map_sqlx_error(error: Error) -> RepositoryError {
+    match error {
+        Error::RowNotFound => RepositoryError::NotFound,
+        Error::Database(ref e)
+            if e.code()
+                .is_some_and(|code| UNIQUE_VIOLATION_CODES.contains(&code.as_ref())) =>
+        {
+            tracing::warn!("{error}");
+            RepositoryError::Conflict {
+                field: conflicting_field(e.as_ref()),
+            }
+        }
+        Error::PoolTimedOut | Error::PoolClosed | Error::Io(_) | Error::Tls(_) => {
+            tracing::error!("{error}");
+            RepositoryError::Unavailable
+        }
+        _ => {
+            tracing::error!("{error}");
+            RepositoryError::Unknown
+        }
+    }
+}
+
+/// The column behind a unique violation. Postgres names the constraint,
+/// `<table>_<column>_key` by convention, SQLite reports `<table>.<column>` or
+/// `index '<name>'` for expression indexes.
+fn conflicting_field(error: &dyn DatabaseError) -> String {
+    if let Some(constraint) = error.constraint() {
+        return column_of_constraint(constraint);
+    }
+
+    let target = error.message().rsplit(": ").next().unwrap_or_default();
+    match target.strip_prefix("index ") {
+        Some(index) => column_of_constraint(index.trim_matches('\'')),
+        None => target
+            .split(", ")
+            .next()
+            .and_then(|column| column.split_once('.'))
+            .map_or(target, |(_, column)| column)
+            .to_string(),
+    }
+}
+
+fn column_of_constraint(name: &str) -> String {
+    name.split_once('_')
+        .and_then(|(_, rest)| rest.strip_suffix("_key"))
+        .unwrap_or(name)
+        .to_string()
+}
+
+/// Timestamp columns are `TIMESTAMP` without a time zone and always hold UTC.
+fn to_utc_primitive(value: OffsetDateTime) -> PrimitiveDateTime {
+    let value = value.to_offset(UtcOffset::UTC);
+
+    PrimitiveDateTime::new(value.date(), value.time())
+}
+
+/// A `LIKE` pattern matching `search` anywhere, with wildcards in `search`
+/// escaped by a backslash.
+fn like_pattern(search: &str) -> String {
+    format!(
+        "%{}%",
+        search
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    )
+}
+
+/// Scopes are stored space separated, e.g. `read write`.
+fn join_scopes(scopes: &[Scope]) -> String {
+    scopes
+        .iter()
+        .map(Scope::as_str)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Unknown scopes are dropped rather than failing the whole row.
+fn parse_scopes(scopes: &str) -> Vec<Scope> {
+    scopes
+        .split_whitespace()
+        .filter_map(|scope| Scope::from_str(scope).ok())
+        .collect()
+}
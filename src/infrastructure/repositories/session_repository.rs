@@ -0,0 +1,115 @@
+use std::{str::FromStr, sync::Arc};
+
+use axum::async_trait;
+use sqlx::{
+    types::{time::PrimitiveDateTime, Uuid},
+    FromRow, Pool, Postgres,
+};
+
+use crate::domain::{
+    clock::Clock,
+    entities::session::Session,
+    id_generator::IdGenerator,
+    repositories::{
+        error::{RepositoryError, RepositoryResult},
+        session_repository::{CreateInput, SessionRepositoryPort},
+    },
+};
+
+use super::{map_sqlx_error, to_utc_primitive};
+
+#[derive(FromRow, Debug)]
+struct SessionDocument {
+    id: Uuid,
+    user_id: Uuid,
+    #[allow(dead_code)]
+    created_at: PrimitiveDateTime,
+    expires_at: PrimitiveDateTime,
+}
+
+impl From<SessionDocument> for Session {
+    fn from(val: SessionDocument) -> Self {
+        Session {
+            id: val.id.into(),
+            user_id: val.user_id.into(),
+            expires_at: val.expires_at.assume_utc(),
+        }
+    }
+}
+
+pub struct SessionRepository {
+    pool: Pool<Postgres>,
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
+}
+
+impl SessionRepository {
+    pub fn new(pool: Pool<Postgres>, clock: Arc<dyn Clock>, ids: Arc<dyn IdGenerator>) -> Self {
+        Self { pool, clock, ids }
+    }
+}
+
+#[async_trait]
+impl SessionRepositoryPort for SessionRepository {
+    #[tracing::instrument(
+        name = "SessionRepository.find_by_id",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn find_by_id(&self, id: String) -> RepositoryResult<Session> {
+        tracing::debug!("SessionRepository.find_by_id");
+
+        let document = sqlx::query_as::<_, SessionDocument>("SELECT * FROM sessions WHERE id = $1")
+            .bind(Uuid::from_str(&id).map_err(|_| RepositoryError::InvalidUuid)?)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "SessionRepository.create",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn create(&self, input: CreateInput) -> RepositoryResult<Session> {
+        tracing::debug!("SessionRepository.create | {}", input.user_id);
+
+        let id = self.ids.generate();
+        let now = to_utc_primitive(self.clock.now());
+
+        let document = sqlx::query_as::<_, SessionDocument>(
+            r#"INSERT INTO sessions
+            (id, user_id, created_at, expires_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *"#,
+        )
+        .bind(id)
+        .bind(input.user_id.as_uuid())
+        .bind(now)
+        .bind(to_utc_primitive(input.expires_at))
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "SessionRepository.delete",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn delete(&self, id: String) -> RepositoryResult<()> {
+        tracing::debug!("SessionRepository.delete");
+
+        sqlx::query("DELETE FROM sessions WHERE id = $1")
+            .bind(Uuid::from_str(&id).map_err(|_| RepositoryError::InvalidUuid)?)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+}
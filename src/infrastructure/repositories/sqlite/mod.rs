@@ -0,0 +1,45 @@
+//! Repositories backed by SQLite, for small deployments that don't warrant a
+//! Postgres server. Ids are stored as hyphenated TEXT and timestamps as UTC
+//! TEXT, otherwise they behave like the Postgres repositories.
+
+use std::str::FromStr;
+
+use sqlx::types::{
+    time::{OffsetDateTime, UtcOffset},
+    Uuid,
+};
+
+use crate::domain::repositories::error::{RepositoryError, RepositoryResult};
+
+use super::{join_scopes, like_pattern, map_sqlx_error, parse_scopes};
+
+pub mod session_repository;
+pub mod todo_repository;
+pub mod token_repository;
+pub mod user_repository;
+
+/// Formats a timestamp like the `strftime('%Y-%m-%d %H:%M:%f', 'now')` column
+/// defaults: UTC with exactly three fractional digits. Stored and bound values
+/// are compared as TEXT, which only orders them chronologically at a fixed
+/// width, whereas sqlx's own encoding drops trailing zeros.
+fn to_timestamp_text(value: OffsetDateTime) -> String {
+    let value = value.to_offset(UtcOffset::UTC);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:03}",
+        value.year(),
+        u8::from(value.month()),
+        value.day(),
+        value.hour(),
+        value.minute(),
+        value.second(),
+        value.millisecond()
+    )
+}
+
+/// Validates an id and brings it into the form it is stored in.
+fn parse_uuid(id: &str) -> RepositoryResult<String> {
+    Uuid::from_str(id)
+        .map(|id| id.to_string())
+        .map_err(|_| RepositoryError::InvalidUuid)
+}
@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use sqlx::{types::time::PrimitiveDateTime, FromRow, Pool, Sqlite};
+use uuid::fmt::Hyphenated;
+
+use crate::domain::{
+    clock::Clock,
+    entities::session::Session,
+    id_generator::IdGenerator,
+    repositories::{
+        error::RepositoryResult,
+        session_repository::{CreateInput, SessionRepositoryPort},
+    },
+};
+
+use super::{map_sqlx_error, parse_uuid, to_timestamp_text};
+
+#[derive(FromRow, Debug)]
+struct SessionDocument {
+    id: String,
+    user_id: Hyphenated,
+    #[allow(dead_code)]
+    created_at: PrimitiveDateTime,
+    expires_at: PrimitiveDateTime,
+}
+
+impl From<SessionDocument> for Session {
+    fn from(val: SessionDocument) -> Self {
+        Session {
+            id: val.id,
+            user_id: val.user_id.into_uuid().into(),
+            expires_at: val.expires_at.assume_utc(),
+        }
+    }
+}
+
+pub struct SqliteSessionRepository {
+    pool: Pool<Sqlite>,
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
+}
+
+impl SqliteSessionRepository {
+    pub fn new(pool: Pool<Sqlite>, clock: Arc<dyn Clock>, ids: Arc<dyn IdGenerator>) -> Self {
+        Self { pool, clock, ids }
+    }
+}
+
+#[async_trait]
+impl SessionRepositoryPort for SqliteSessionRepository {
+    #[tracing::instrument(
+        name = "SqliteSessionRepository.find_by_id",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn find_by_id(&self, id: String) -> RepositoryResult<Session> {
+        tracing::debug!("SqliteSessionRepository.find_by_id");
+
+        let document = sqlx::query_as::<_, SessionDocument>("SELECT * FROM sessions WHERE id = ?")
+            .bind(parse_uuid(&id)?)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "SqliteSessionRepository.create",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn create(&self, input: CreateInput) -> RepositoryResult<Session> {
+        tracing::debug!("SqliteSessionRepository.create | {}", input.user_id);
+
+        let id = self.ids.generate().to_string();
+        let now = to_timestamp_text(self.clock.now());
+
+        let document = sqlx::query_as::<_, SessionDocument>(
+            r#"INSERT INTO sessions
+            (id, user_id, created_at, expires_at)
+            VALUES (?, ?, ?, ?)
+            RETURNING *"#,
+        )
+        .bind(id)
+        .bind(input.user_id.to_string())
+        .bind(now)
+        .bind(to_timestamp_text(input.expires_at))
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "SqliteSessionRepository.delete",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn delete(&self, id: String) -> RepositoryResult<()> {
+        tracing::debug!("SqliteSessionRepository.delete");
+
+        sqlx::query("DELETE FROM sessions WHERE id = ?")
+            .bind(parse_uuid(&id)?)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,414 @@
+use std::{str::FromStr, sync::Arc};
+
+use axum::async_trait;
+use sqlx::{
+    types::time::{OffsetDateTime, PrimitiveDateTime},
+    FromRow, Pool, QueryBuilder, Sqlite,
+};
+use uuid::fmt::Hyphenated;
+
+use crate::domain::{
+    clock::Clock,
+    entities::todo::{Priority, Todo},
+    id_generator::IdGenerator,
+    ids::{TodoId, UserId},
+    repositories::{
+        error::{RepositoryError, RepositoryResult},
+        pagination::{CursorValue, SortDirection},
+        todo_repository::{
+            CreateInput, ListQuery, TodoCursor, TodoPage, TodoRepositoryPort, TodoSortField,
+            UpdateInput,
+        },
+    },
+    values::TodoTitle,
+};
+
+use super::{like_pattern, map_sqlx_error, parse_uuid, to_timestamp_text};
+
+#[derive(FromRow, Debug)]
+struct TodoDocument {
+    id: Hyphenated,
+    owner_id: Hyphenated,
+    title: String,
+    description: String,
+    priority: String,
+    due_at: Option<PrimitiveDateTime>,
+    completed_at: Option<PrimitiveDateTime>,
+    deleted_at: Option<PrimitiveDateTime>,
+    version: i64,
+    created_at: PrimitiveDateTime,
+    updated_at: PrimitiveDateTime,
+}
+
+impl From<TodoDocument> for Todo {
+    fn from(val: TodoDocument) -> Self {
+        Todo {
+            id: val.id.into_uuid().into(),
+            owner_id: val.owner_id.into_uuid().into(),
+            title: val.title,
+            description: val.description,
+            priority: Priority::from_str(&val.priority).unwrap_or_default(),
+            due_at: val.due_at.map(PrimitiveDateTime::assume_utc),
+            completed_at: val.completed_at.map(PrimitiveDateTime::assume_utc),
+            deleted_at: val.deleted_at.map(PrimitiveDateTime::assume_utc),
+            version: val.version,
+            created_at: val.created_at.assume_utc(),
+            updated_at: val.updated_at.assume_utc(),
+        }
+    }
+}
+
+pub struct SqliteTodoRepository {
+    pool: Pool<Sqlite>,
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
+}
+
+impl SqliteTodoRepository {
+    pub fn new(pool: Pool<Sqlite>, clock: Arc<dyn Clock>, ids: Arc<dyn IdGenerator>) -> Self {
+        Self { pool, clock, ids }
+    }
+}
+
+#[async_trait]
+impl TodoRepositoryPort for SqliteTodoRepository {
+    #[tracing::instrument(
+        name = "SqliteTodoRepository.list",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn list(&self, query: ListQuery) -> RepositoryResult<TodoPage> {
+        tracing::debug!("SqliteTodoRepository.list | {query:?}");
+
+        let sort_column = match query.sort_by {
+            TodoSortField::CreatedAt => "created_at",
+            TodoSortField::UpdatedAt => "updated_at",
+            TodoSortField::Title => "title",
+        };
+        let (order, comparison) = match query.direction {
+            SortDirection::Asc => ("ASC", ">"),
+            SortDirection::Desc => ("DESC", "<"),
+        };
+
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            "SELECT * FROM todos WHERE deleted_at IS NULL AND owner_id = ",
+        );
+        builder.push_bind(query.owner_id.to_string());
+
+        match query.completed {
+            Some(true) => builder.push(" AND completed_at IS NOT NULL"),
+            Some(false) => builder.push(" AND completed_at IS NULL"),
+            None => &mut builder,
+        };
+
+        // Unlike ILIKE, LIKE only folds the case of ASCII letters
+        if let Some(search) = query.search {
+            let pattern = like_pattern(&search);
+            builder
+                .push(" AND (title LIKE ")
+                .push_bind(pattern.clone())
+                .push(" ESCAPE '\\' OR description LIKE ")
+                .push_bind(pattern)
+                .push(" ESCAPE '\\')");
+        }
+
+        for (column, operator, value) in [
+            ("created_at", ">=", query.created_after),
+            ("created_at", "<", query.created_before),
+            ("updated_at", ">=", query.updated_after),
+            ("updated_at", "<", query.updated_before),
+        ] {
+            if let Some(value) = value {
+                builder
+                    .push(format!(" AND {column} {operator} "))
+                    .push_bind(to_timestamp_text(value));
+            }
+        }
+
+        if let Some(cursor) = query.after {
+            builder.push(format!(" AND ({sort_column}, id) {comparison} ("));
+            match cursor.value {
+                CursorValue::Timestamp(value) => builder.push_bind(to_timestamp_text(value)),
+                CursorValue::Text(value) => builder.push_bind(value),
+            };
+            builder
+                .push(", ")
+                .push_bind(parse_uuid(&cursor.id)?)
+                .push(")");
+        }
+
+        builder
+            .push(format!(
+                " ORDER BY {sort_column} {order}, id {order} LIMIT "
+            ))
+            .push_bind(i64::from(query.limit) + 1);
+
+        let mut documents = builder
+            .build_query_as::<TodoDocument>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let next_cursor = if documents.len() > query.limit as usize {
+            documents.truncate(query.limit as usize);
+            documents.last().map(|last| TodoCursor {
+                sort_by: query.sort_by,
+                direction: query.direction,
+                value: match query.sort_by {
+                    TodoSortField::CreatedAt => {
+                        CursorValue::Timestamp(last.created_at.assume_utc())
+                    }
+                    TodoSortField::UpdatedAt => {
+                        CursorValue::Timestamp(last.updated_at.assume_utc())
+                    }
+                    TodoSortField::Title => CursorValue::Text(last.title.clone()),
+                },
+                id: last.id.to_string(),
+            })
+        } else {
+            None
+        };
+
+        Ok(TodoPage {
+            items: documents.into_iter().map(|doc| doc.into()).collect(),
+            next_cursor,
+        })
+    }
+
+    #[tracing::instrument(
+        name = "SqliteTodoRepository.find_by_id",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn find_by_id(&self, id: TodoId) -> RepositoryResult<Todo> {
+        tracing::debug!("SqliteTodoRepository.find_by_id | {id}");
+
+        let document = sqlx::query_as::<_, TodoDocument>(
+            "SELECT * FROM todos WHERE id = ? AND deleted_at IS NULL",
+        )
+        .bind(id.to_string())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "SqliteTodoRepository.find_by_owner_and_id",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn find_by_owner_and_id(&self, owner_id: UserId, id: TodoId) -> RepositoryResult<Todo> {
+        tracing::debug!("SqliteTodoRepository.find_by_owner_and_id | {owner_id} | {id}");
+
+        let document = sqlx::query_as::<_, TodoDocument>(
+            "SELECT * FROM todos WHERE id = ? AND owner_id = ? AND deleted_at IS NULL",
+        )
+        .bind(id.to_string())
+        .bind(owner_id.to_string())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "SqliteTodoRepository.update_one",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn update_one(&self, input: UpdateInput) -> RepositoryResult<Todo> {
+        tracing::debug!("SqliteTodoRepository.update_one | {input:?}");
+
+        let id = input.id;
+
+        // A single conditional statement, so concurrent updates cannot
+        // overwrite each other's changes
+        let document = sqlx::query_as::<_, TodoDocument>(
+            r#"UPDATE todos
+            SET
+            title = COALESCE(?, title),
+            description = COALESCE(?, description),
+            priority = COALESCE(?, priority),
+            due_at = CASE WHEN ? THEN ? ELSE due_at END,
+            completed_at = CASE WHEN ? THEN ? ELSE completed_at END,
+            updated_at = ?,
+            version = version + 1
+            WHERE id = ? AND deleted_at IS NULL AND (? IS NULL OR version = ?)
+            RETURNING *"#,
+        )
+        .bind(input.title.map(TodoTitle::into_inner))
+        .bind(input.description)
+        .bind(input.priority.map(|priority| priority.as_str()))
+        .bind(input.due_at.is_some())
+        .bind(input.due_at.flatten().map(to_timestamp_text))
+        .bind(input.completed_at.is_some())
+        .bind(input.completed_at.flatten().map(to_timestamp_text))
+        .bind(to_timestamp_text(self.clock.now()))
+        .bind(id.to_string())
+        .bind(input.version)
+        .bind(input.version)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error);
+
+        match document {
+            Ok(document) => Ok(document.into()),
+            Err(RepositoryError::NotFound) if input.version.is_some() => {
+                // Tell a stale version apart from a todo that does not exist
+                self.find_by_id(id).await?;
+
+                Err(RepositoryError::VersionMismatch)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "SqliteTodoRepository.create",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn create(&self, input: CreateInput) -> RepositoryResult<Todo> {
+        tracing::debug!("SqliteTodoRepository.create | {input:?}");
+
+        let id = self.ids.generate().to_string();
+        let now = to_timestamp_text(self.clock.now());
+
+        let document = sqlx::query_as::<_, TodoDocument>(
+            r#"INSERT INTO todos
+            (id, owner_id, title, description, priority, due_at, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING *"#,
+        )
+        .bind(id)
+        .bind(input.owner_id.to_string())
+        .bind(input.title.into_inner())
+        .bind(input.description)
+        .bind(input.priority.as_str())
+        .bind(input.due_at.map(to_timestamp_text))
+        .bind(&now)
+        .bind(&now)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "SqliteTodoRepository.delete",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn delete(&self, id: TodoId) -> RepositoryResult<()> {
+        tracing::debug!("SqliteTodoRepository.delete | {id}");
+
+        let now = to_timestamp_text(self.clock.now());
+
+        let result = sqlx::query(
+            r#"UPDATE todos
+                SET deleted_at = ?, updated_at = ?, version = version + 1
+                WHERE id = ? AND deleted_at IS NULL"#,
+        )
+        .bind(&now)
+        .bind(&now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "SqliteTodoRepository.list_deleted_by_owner",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn list_deleted_by_owner(&self, owner_id: UserId) -> RepositoryResult<Vec<Todo>> {
+        tracing::debug!("SqliteTodoRepository.list_deleted_by_owner | {owner_id}");
+
+        let documents = sqlx::query_as::<_, TodoDocument>(
+            r#"SELECT * FROM todos
+            WHERE owner_id = ? AND deleted_at IS NOT NULL
+            ORDER BY deleted_at DESC"#,
+        )
+        .bind(owner_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(documents.into_iter().map(|doc| doc.into()).collect())
+    }
+
+    #[tracing::instrument(
+        name = "SqliteTodoRepository.find_deleted_by_owner_and_id",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn find_deleted_by_owner_and_id(
+        &self,
+        owner_id: UserId,
+        id: TodoId,
+    ) -> RepositoryResult<Todo> {
+        tracing::debug!("SqliteTodoRepository.find_deleted_by_owner_and_id | {owner_id} | {id}");
+
+        let document = sqlx::query_as::<_, TodoDocument>(
+            "SELECT * FROM todos WHERE id = ? AND owner_id = ? AND deleted_at IS NOT NULL",
+        )
+        .bind(id.to_string())
+        .bind(owner_id.to_string())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "SqliteTodoRepository.restore",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn restore(&self, id: TodoId) -> RepositoryResult<Todo> {
+        tracing::debug!("SqliteTodoRepository.restore | {id}");
+
+        let document = sqlx::query_as::<_, TodoDocument>(
+            r#"UPDATE todos
+            SET deleted_at = NULL, updated_at = ?, version = version + 1
+            WHERE id = ? AND deleted_at IS NOT NULL
+            RETURNING *"#,
+        )
+        .bind(to_timestamp_text(self.clock.now()))
+        .bind(id.to_string())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "SqliteTodoRepository.purge_deleted",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn purge_deleted(&self, deleted_before: OffsetDateTime) -> RepositoryResult<u64> {
+        tracing::debug!("SqliteTodoRepository.purge_deleted | {deleted_before}");
+
+        let result = sqlx::query("DELETE FROM todos WHERE deleted_at < ?")
+            .bind(to_timestamp_text(deleted_before))
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use sqlx::{
+    types::time::{OffsetDateTime, PrimitiveDateTime},
+    FromRow, Pool, Sqlite,
+};
+use uuid::fmt::Hyphenated;
+
+use crate::domain::{
+    clock::Clock,
+    entities::token::Token,
+    id_generator::IdGenerator,
+    ids::{TokenId, UserId},
+    repositories::{
+        error::{RepositoryError, RepositoryResult},
+        token_repository::{CreateInput, TokenRepositoryPort},
+    },
+};
+
+use super::{join_scopes, map_sqlx_error, parse_scopes, to_timestamp_text};
+
+#[derive(FromRow, Debug)]
+struct TokenDocument {
+    id: Hyphenated,
+    user_id: Hyphenated,
+    name: String,
+    #[allow(dead_code)]
+    token_hash: String,
+    scopes: String,
+    expires_at: Option<PrimitiveDateTime>,
+    last_used_at: Option<PrimitiveDateTime>,
+    created_at: PrimitiveDateTime,
+}
+
+impl From<TokenDocument> for Token {
+    fn from(val: TokenDocument) -> Self {
+        Token {
+            id: val.id.into_uuid().into(),
+            user_id: val.user_id.into_uuid().into(),
+            name: val.name,
+            scopes: parse_scopes(&val.scopes),
+            expires_at: val.expires_at.map(PrimitiveDateTime::assume_utc),
+            last_used_at: val.last_used_at.map(PrimitiveDateTime::assume_utc),
+            created_at: val.created_at.assume_utc(),
+        }
+    }
+}
+
+pub struct SqliteTokenRepository {
+    pool: Pool<Sqlite>,
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
+}
+
+impl SqliteTokenRepository {
+    pub fn new(pool: Pool<Sqlite>, clock: Arc<dyn Clock>, ids: Arc<dyn IdGenerator>) -> Self {
+        Self { pool, clock, ids }
+    }
+}
+
+#[async_trait]
+impl TokenRepositoryPort for SqliteTokenRepository {
+    #[tracing::instrument(
+        name = "SqliteTokenRepository.find_by_hash",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn find_by_hash(&self, token_hash: String) -> RepositoryResult<Token> {
+        tracing::debug!("SqliteTokenRepository.find_by_hash");
+
+        let document =
+            sqlx::query_as::<_, TokenDocument>("SELECT * FROM tokens WHERE token_hash = ?")
+                .bind(token_hash)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "SqliteTokenRepository.list_by_user",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn list_by_user(&self, user_id: UserId) -> RepositoryResult<Vec<Token>> {
+        tracing::debug!("SqliteTokenRepository.list_by_user | {user_id}");
+
+        let documents = sqlx::query_as::<_, TokenDocument>(
+            "SELECT * FROM tokens WHERE user_id = ? ORDER BY created_at, id",
+        )
+        .bind(user_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(documents.into_iter().map(Token::from).collect())
+    }
+
+    #[tracing::instrument(
+        name = "SqliteTokenRepository.create",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn create(&self, input: CreateInput) -> RepositoryResult<Token> {
+        tracing::debug!("SqliteTokenRepository.create | {}", input.user_id);
+
+        let id = self.ids.generate().to_string();
+        let now = to_timestamp_text(self.clock.now());
+
+        let document = sqlx::query_as::<_, TokenDocument>(
+            r#"INSERT INTO tokens
+            (id, user_id, name, token_hash, scopes, expires_at, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            RETURNING *"#,
+        )
+        .bind(id)
+        .bind(input.user_id.to_string())
+        .bind(input.name)
+        .bind(input.token_hash)
+        .bind(join_scopes(&input.scopes))
+        .bind(input.expires_at.map(to_timestamp_text))
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "SqliteTokenRepository.touch",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn touch(&self, id: TokenId, used_at: OffsetDateTime) -> RepositoryResult<()> {
+        tracing::debug!("SqliteTokenRepository.touch | {id}");
+
+        sqlx::query("UPDATE tokens SET last_used_at = ? WHERE id = ?")
+            .bind(to_timestamp_text(used_at))
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "SqliteTokenRepository.delete",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn delete(&self, user_id: UserId, id: TokenId) -> RepositoryResult<()> {
+        tracing::debug!("SqliteTokenRepository.delete | {id}");
+
+        let result = sqlx::query("DELETE FROM tokens WHERE id = ? AND user_id = ?")
+            .bind(id.to_string())
+            .bind(user_id.to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,279 @@
+use std::{str::FromStr, sync::Arc};
+
+use axum::async_trait;
+use sqlx::{
+    types::time::{OffsetDateTime, PrimitiveDateTime},
+    Error, FromRow, Pool, QueryBuilder, Sqlite,
+};
+use uuid::fmt::Hyphenated;
+
+use crate::domain::{
+    clock::Clock,
+    entities::user::{Role, User},
+    id_generator::IdGenerator,
+    ids::UserId,
+    repositories::{
+        error::{RepositoryError, RepositoryResult},
+        user_repository::{CreateInput, UpdateInput, UserRepositoryPort},
+    },
+    values::{Email, FirstName},
+};
+
+use super::{like_pattern, map_sqlx_error, to_timestamp_text};
+
+#[derive(FromRow, Debug)]
+struct UserDocument {
+    id: Hyphenated,
+    email: String,
+    first_name: String,
+    password_hash: Option<String>,
+    role: String,
+    version: i64,
+    created_at: PrimitiveDateTime,
+    updated_at: PrimitiveDateTime,
+}
+
+impl From<UserDocument> for User {
+    fn from(val: UserDocument) -> Self {
+        User {
+            id: val.id.into_uuid().into(),
+            email: val.email,
+            first_name: val.first_name,
+            password_hash: val.password_hash,
+            role: Role::from_str(&val.role).unwrap_or_default(),
+            version: val.version,
+            created_at: val.created_at.assume_utc(),
+            updated_at: val.updated_at.assume_utc(),
+        }
+    }
+}
+
+pub struct SqliteUserRepository {
+    pool: Pool<Sqlite>,
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
+}
+
+impl SqliteUserRepository {
+    pub fn new(pool: Pool<Sqlite>, clock: Arc<dyn Clock>, ids: Arc<dyn IdGenerator>) -> Self {
+        Self { pool, clock, ids }
+    }
+}
+
+#[async_trait]
+impl UserRepositoryPort for SqliteUserRepository {
+    #[tracing::instrument(
+        name = "SqliteUserRepository.find_by_id",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn find_by_id(&self, id: UserId) -> RepositoryResult<User> {
+        tracing::debug!("SqliteUserRepository.find_by_id | {id}");
+
+        let document = sqlx::query_as::<_, UserDocument>(
+            "SELECT * FROM users WHERE id = ? AND deleted_at IS NULL",
+        )
+        .bind(id.to_string())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "SqliteUserRepository.find_by_email",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn find_by_email(&self, email: String) -> RepositoryResult<Option<User>> {
+        tracing::debug!("SqliteUserRepository.find_by_email | {email}");
+
+        let document = sqlx::query_as::<_, UserDocument>(
+            "SELECT * FROM users WHERE LOWER(email) = LOWER(?) AND deleted_at IS NULL",
+        )
+        .bind(email)
+        .fetch_one(&self.pool)
+        .await;
+
+        match document {
+            Ok(doc) => Ok(Some(doc.into())),
+            Err(Error::RowNotFound) => Ok(None),
+            Err(e) => Err(map_sqlx_error(e)),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "SqliteUserRepository.list",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn list(&self, search: Option<String>) -> RepositoryResult<Vec<User>> {
+        tracing::debug!("SqliteUserRepository.list | {search:?}");
+
+        let mut builder =
+            QueryBuilder::<Sqlite>::new("SELECT * FROM users WHERE deleted_at IS NULL");
+        if let Some(search) = search {
+            let pattern = like_pattern(&search);
+            builder
+                .push(" AND (email LIKE ")
+                .push_bind(pattern.clone())
+                .push(" ESCAPE '\\' OR first_name LIKE ")
+                .push_bind(pattern)
+                .push(" ESCAPE '\\')");
+        }
+        builder.push(" ORDER BY email");
+
+        let documents = builder
+            .build_query_as::<UserDocument>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(documents.into_iter().map(User::from).collect())
+    }
+
+    #[tracing::instrument(
+        name = "SqliteUserRepository.update_one",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn update_one(&self, id: UserId, input: UpdateInput) -> RepositoryResult<User> {
+        tracing::debug!("SqliteUserRepository.update_one | {id} | {input:?}");
+
+        let document = sqlx::query_as::<_, UserDocument>(
+            r#"UPDATE users
+            SET
+            email = COALESCE(?, email),
+            first_name = COALESCE(?, first_name),
+            updated_at = ?,
+            version = version + 1
+            WHERE id = ? AND deleted_at IS NULL AND (? IS NULL OR version = ?)
+            RETURNING *"#,
+        )
+        .bind(input.email.map(Email::into_inner))
+        .bind(input.first_name.map(FirstName::into_inner))
+        .bind(to_timestamp_text(self.clock.now()))
+        .bind(id.to_string())
+        .bind(input.version)
+        .bind(input.version)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error);
+
+        match document {
+            Ok(document) => Ok(document.into()),
+            Err(RepositoryError::NotFound) if input.version.is_some() => {
+                // Tell a stale version apart from a user that does not exist
+                self.find_by_id(id).await?;
+
+                Err(RepositoryError::VersionMismatch)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "SqliteUserRepository.create",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn create(&self, input: CreateInput) -> RepositoryResult<User> {
+        tracing::debug!("SqliteUserRepository.create | {input:?}");
+
+        let id = self.ids.generate().to_string();
+        let now = to_timestamp_text(self.clock.now());
+
+        let document = sqlx::query_as::<_, UserDocument>(
+            r#"INSERT INTO users
+            (id, email, first_name, password_hash, role, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            RETURNING *"#,
+        )
+        .bind(id)
+        .bind(input.email.into_inner())
+        .bind(input.first_name.into_inner())
+        .bind(input.password_hash)
+        .bind(input.role.as_str())
+        .bind(&now)
+        .bind(&now)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "SqliteUserRepository.set_role",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn set_role(&self, id: UserId, role: Role) -> RepositoryResult<User> {
+        tracing::debug!("SqliteUserRepository.set_role | {id} | {role:?}");
+
+        let document = sqlx::query_as::<_, UserDocument>(
+            r#"UPDATE users
+            SET
+            role = ?,
+            updated_at = ?,
+            version = version + 1
+            WHERE id = ? AND deleted_at IS NULL
+            RETURNING *"#,
+        )
+        .bind(role.as_str())
+        .bind(to_timestamp_text(self.clock.now()))
+        .bind(id.to_string())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "SqliteUserRepository.delete",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn delete(&self, id: UserId) -> RepositoryResult<()> {
+        tracing::debug!("SqliteUserRepository.delete | {id}");
+
+        let now = to_timestamp_text(self.clock.now());
+
+        let result = sqlx::query(
+            r#"UPDATE users
+                SET deleted_at = ?, updated_at = ?, version = version + 1
+                WHERE id = ? AND deleted_at IS NULL"#,
+        )
+        .bind(&now)
+        .bind(&now)
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "SqliteUserRepository.purge_deleted",
+        skip_all,
+        fields(otel.kind = "client", db.system = "sqlite")
+    )]
+    async fn purge_deleted(&self, deleted_before: OffsetDateTime) -> RepositoryResult<u64> {
+        tracing::debug!("SqliteUserRepository.purge_deleted | {deleted_before}");
+
+        let result = sqlx::query("DELETE FROM users WHERE deleted_at < ?")
+            .bind(to_timestamp_text(deleted_before))
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{str::FromStr, sync::Arc};
 
 use axum::async_trait;
 use sqlx::{
@@ -6,140 +6,400 @@ use sqlx::{
         time::{OffsetDateTime, PrimitiveDateTime},
         Uuid,
     },
-    Error, FromRow,
+    FromRow, Pool, Postgres, QueryBuilder,
 };
 
-use crate::{
-    domain::{
-        entities::todo::Todo,
-        repositories::{
-            error::{RepositoryError, RepositoryResult},
-            todo_repository::{CreateInput, TodoRepositoryPort, UpdateInput},
+use crate::domain::{
+    clock::Clock,
+    entities::todo::{Priority, Todo},
+    id_generator::IdGenerator,
+    ids::{TodoId, UserId},
+    repositories::{
+        error::{RepositoryError, RepositoryResult},
+        pagination::{CursorValue, SortDirection},
+        todo_repository::{
+            CreateInput, ListQuery, TodoCursor, TodoPage, TodoRepositoryPort, TodoSortField,
+            UpdateInput,
         },
     },
-    infrastructure::Database,
+    values::TodoTitle,
 };
 
+use super::{like_pattern, map_sqlx_error, to_utc_primitive};
+
 #[derive(FromRow, Debug)]
 struct TodoDocument {
     id: Uuid,
+    owner_id: Uuid,
     title: String,
     description: String,
-    #[allow(dead_code)]
-    created_at: PrimitiveDateTime,
-    #[allow(dead_code)]
-    updated_at: PrimitiveDateTime,
+    priority: String,
+    due_at: Option<PrimitiveDateTime>,
+    completed_at: Option<PrimitiveDateTime>,
+    deleted_at: Option<PrimitiveDateTime>,
+    version: i64,
+    created_at: OffsetDateTime,
+    updated_at: OffsetDateTime,
 }
 
 impl From<TodoDocument> for Todo {
     fn from(val: TodoDocument) -> Self {
         Todo {
-            id: val.id.to_string(),
+            id: val.id.into(),
+            owner_id: val.owner_id.into(),
             title: val.title,
             description: val.description,
+            priority: Priority::from_str(&val.priority).unwrap_or_default(),
+            due_at: val.due_at.map(PrimitiveDateTime::assume_utc),
+            completed_at: val.completed_at.map(PrimitiveDateTime::assume_utc),
+            deleted_at: val.deleted_at.map(PrimitiveDateTime::assume_utc),
+            version: val.version,
+            created_at: val.created_at,
+            updated_at: val.updated_at,
         }
     }
 }
 
 pub struct TodoRepository {
-    db: Database,
+    pool: Pool<Postgres>,
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
 }
 
 impl TodoRepository {
-    pub fn new(db: Database) -> Self {
-        Self { db }
+    pub fn new(pool: Pool<Postgres>, clock: Arc<dyn Clock>, ids: Arc<dyn IdGenerator>) -> Self {
+        Self { pool, clock, ids }
     }
 }
 
 #[async_trait]
 impl TodoRepositoryPort for TodoRepository {
-    async fn list(&self) -> RepositoryResult<Vec<Todo>> {
-        tracing::debug!("TodoRepository.list");
+    #[tracing::instrument(
+        name = "TodoRepository.list",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn list(&self, query: ListQuery) -> RepositoryResult<TodoPage> {
+        tracing::debug!("TodoRepository.list | {query:?}");
+
+        let sort_column = match query.sort_by {
+            TodoSortField::CreatedAt => "created_at",
+            TodoSortField::UpdatedAt => "updated_at",
+            TodoSortField::Title => "title",
+        };
+        let (order, comparison) = match query.direction {
+            SortDirection::Asc => ("ASC", ">"),
+            SortDirection::Desc => ("DESC", "<"),
+        };
+
+        let mut builder = QueryBuilder::<Postgres>::new(
+            "SELECT * FROM todos WHERE deleted_at IS NULL AND owner_id = ",
+        );
+        builder.push_bind(query.owner_id.as_uuid());
+
+        match query.completed {
+            Some(true) => builder.push(" AND completed_at IS NOT NULL"),
+            Some(false) => builder.push(" AND completed_at IS NULL"),
+            None => &mut builder,
+        };
+
+        if let Some(search) = query.search {
+            let pattern = like_pattern(&search);
+            builder
+                .push(" AND (title ILIKE ")
+                .push_bind(pattern.clone())
+                .push(" OR description ILIKE ")
+                .push_bind(pattern)
+                .push(")");
+        }
+
+        for (column, operator, value) in [
+            ("created_at", ">=", query.created_after),
+            ("created_at", "<", query.created_before),
+            ("updated_at", ">=", query.updated_after),
+            ("updated_at", "<", query.updated_before),
+        ] {
+            if let Some(value) = value {
+                builder
+                    .push(format!(" AND {column} {operator} "))
+                    .push_bind(value);
+            }
+        }
 
-        let documents = sqlx::query_as::<_, TodoDocument>("SELECT * FROM todos")
-            .fetch_all(&self.db.pool())
+        if let Some(cursor) = query.after {
+            builder.push(format!(" AND ({sort_column}, id) {comparison} ("));
+            match cursor.value {
+                CursorValue::Timestamp(value) => builder.push_bind(value),
+                CursorValue::Text(value) => builder.push_bind(value),
+            };
+            builder
+                .push(", ")
+                .push_bind(Uuid::from_str(&cursor.id).map_err(|_| RepositoryError::InvalidUuid)?)
+                .push(")");
+        }
+
+        builder
+            .push(format!(
+                " ORDER BY {sort_column} {order}, id {order} LIMIT "
+            ))
+            .push_bind(i64::from(query.limit) + 1);
+
+        let mut documents = builder
+            .build_query_as::<TodoDocument>()
+            .fetch_all(&self.pool)
             .await
-            .map_err(|_| RepositoryError::Unknown)?;
+            .map_err(map_sqlx_error)?;
 
-        Ok(documents.into_iter().map(|doc| doc.into()).collect())
+        let next_cursor = if documents.len() > query.limit as usize {
+            documents.truncate(query.limit as usize);
+            documents.last().map(|last| TodoCursor {
+                sort_by: query.sort_by,
+                direction: query.direction,
+                value: match query.sort_by {
+                    TodoSortField::CreatedAt => CursorValue::Timestamp(last.created_at),
+                    TodoSortField::UpdatedAt => CursorValue::Timestamp(last.updated_at),
+                    TodoSortField::Title => CursorValue::Text(last.title.clone()),
+                },
+                id: last.id.to_string(),
+            })
+        } else {
+            None
+        };
+
+        Ok(TodoPage {
+            items: documents.into_iter().map(|doc| doc.into()).collect(),
+            next_cursor,
+        })
     }
 
-    async fn find_by_id(&self, id: String) -> RepositoryResult<Todo> {
+    #[tracing::instrument(
+        name = "TodoRepository.find_by_id",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn find_by_id(&self, id: TodoId) -> RepositoryResult<Todo> {
         tracing::debug!("TodoRepository.find_by_id | {id}");
 
-        let document = sqlx::query_as::<_, TodoDocument>("SELECT * FROM todos WHERE id = $1")
-            .bind(Uuid::from_str(&id).map_err(|_| RepositoryError::InvalidUuid)?)
-            .fetch_one(&self.db.pool())
-            .await
-            .map_err(|e| {
-                tracing::error!("{e}");
-                match e {
-                    Error::RowNotFound => RepositoryError::NotFound,
-                    _ => RepositoryError::Unknown,
-                }
-            })?;
+        let document = sqlx::query_as::<_, TodoDocument>(
+            "SELECT * FROM todos WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id.as_uuid())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
 
         Ok(document.into())
     }
 
+    #[tracing::instrument(
+        name = "TodoRepository.find_by_owner_and_id",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn find_by_owner_and_id(&self, owner_id: UserId, id: TodoId) -> RepositoryResult<Todo> {
+        tracing::debug!("TodoRepository.find_by_owner_and_id | {owner_id} | {id}");
+
+        let document = sqlx::query_as::<_, TodoDocument>(
+            "SELECT * FROM todos WHERE id = $1 AND owner_id = $2 AND deleted_at IS NULL",
+        )
+        .bind(id.as_uuid())
+        .bind(owner_id.as_uuid())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "TodoRepository.update_one",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
     async fn update_one(&self, input: UpdateInput) -> RepositoryResult<Todo> {
         tracing::debug!("TodoRepository.update_one | {input:?}");
 
-        let document = self.find_by_id(input.id).await?;
-        let now = OffsetDateTime::now_utc();
-        let now = PrimitiveDateTime::new(now.date(), now.time());
+        let id = input.id;
 
+        // A single conditional statement, so concurrent updates cannot
+        // overwrite each other's changes
         let document = sqlx::query_as::<_, TodoDocument>(
             r#"UPDATE todos
             SET
-            title = $1,
-            description = $2,
-            updated_at = $3
-            WHERE id = $4
+            title = COALESCE($1, title),
+            description = COALESCE($2, description),
+            priority = COALESCE($3, priority),
+            due_at = CASE WHEN $4 THEN $5 ELSE due_at END,
+            completed_at = CASE WHEN $6 THEN $7 ELSE completed_at END,
+            updated_at = $8,
+            version = version + 1
+            WHERE id = $9 AND deleted_at IS NULL AND ($10::BIGINT IS NULL OR version = $10)
             RETURNING *"#,
         )
-        .bind(input.title.unwrap_or(document.title))
-        .bind(input.description.unwrap_or(document.description))
-        .bind(now)
-        .bind(Uuid::from_str(&document.id).map_err(|_| RepositoryError::InvalidUuid)?)
-        .fetch_one(&self.db.pool())
+        .bind(input.title.map(TodoTitle::into_inner))
+        .bind(input.description)
+        .bind(input.priority.map(|priority| priority.as_str()))
+        .bind(input.due_at.is_some())
+        .bind(input.due_at.flatten().map(to_utc_primitive))
+        .bind(input.completed_at.is_some())
+        .bind(input.completed_at.flatten().map(to_utc_primitive))
+        .bind(self.clock.now())
+        .bind(id.as_uuid())
+        .bind(input.version)
+        .fetch_one(&self.pool)
         .await
-        .map_err(|e| {
-            tracing::error!("{e}");
-            match e {
-                Error::RowNotFound => RepositoryError::NotFound,
-                _ => RepositoryError::Unknown,
-            }
-        })?;
+        .map_err(map_sqlx_error);
 
-        Ok(document.into())
+        match document {
+            Ok(document) => Ok(document.into()),
+            Err(RepositoryError::NotFound) if input.version.is_some() => {
+                // Tell a stale version apart from a todo that does not exist
+                self.find_by_id(id).await?;
+
+                Err(RepositoryError::VersionMismatch)
+            }
+            Err(e) => Err(e),
+        }
     }
 
+    #[tracing::instrument(
+        name = "TodoRepository.create",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
     async fn create(&self, input: CreateInput) -> RepositoryResult<Todo> {
         tracing::debug!("TodoRepository.create | {input:?}");
 
-        let id = Uuid::new_v4();
-        let now = OffsetDateTime::now_utc();
-        let now = PrimitiveDateTime::new(now.date(), now.time());
+        let id = self.ids.generate();
 
         let document = sqlx::query_as::<_, TodoDocument>(
             r#"INSERT INTO todos
-            (id, title, description, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5)
+            (id, owner_id, title, description, priority, due_at, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $7)
             RETURNING *"#,
         )
         .bind(id)
-        .bind(input.title)
+        .bind(input.owner_id.as_uuid())
+        .bind(input.title.into_inner())
         .bind(input.description)
-        .bind(now)
-        .bind(now)
-        .fetch_one(&self.db.pool())
+        .bind(input.priority.as_str())
+        .bind(input.due_at.map(to_utc_primitive))
+        .bind(self.clock.now())
+        .fetch_one(&self.pool)
         .await
-        .map_err(|e| {
-            tracing::error!("{e}");
-            RepositoryError::Unknown
-        })?;
+        .map_err(map_sqlx_error)?;
 
         Ok(document.into())
     }
+
+    #[tracing::instrument(
+        name = "TodoRepository.delete",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn delete(&self, id: TodoId) -> RepositoryResult<()> {
+        tracing::debug!("TodoRepository.delete | {id}");
+
+        let now = self.clock.now();
+
+        let result =
+            sqlx::query("UPDATE todos SET deleted_at = $1, updated_at = $2, version = version + 1 WHERE id = $3 AND deleted_at IS NULL")
+                .bind(to_utc_primitive(now))
+                .bind(now)
+                .bind(id.as_uuid())
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlx_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "TodoRepository.list_deleted_by_owner",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn list_deleted_by_owner(&self, owner_id: UserId) -> RepositoryResult<Vec<Todo>> {
+        tracing::debug!("TodoRepository.list_deleted_by_owner | {owner_id}");
+
+        let documents = sqlx::query_as::<_, TodoDocument>(
+            r#"SELECT * FROM todos
+            WHERE owner_id = $1 AND deleted_at IS NOT NULL
+            ORDER BY deleted_at DESC"#,
+        )
+        .bind(owner_id.as_uuid())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(documents.into_iter().map(|doc| doc.into()).collect())
+    }
+
+    #[tracing::instrument(
+        name = "TodoRepository.find_deleted_by_owner_and_id",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn find_deleted_by_owner_and_id(
+        &self,
+        owner_id: UserId,
+        id: TodoId,
+    ) -> RepositoryResult<Todo> {
+        tracing::debug!("TodoRepository.find_deleted_by_owner_and_id | {owner_id} | {id}");
+
+        let document = sqlx::query_as::<_, TodoDocument>(
+            "SELECT * FROM todos WHERE id = $1 AND owner_id = $2 AND deleted_at IS NOT NULL",
+        )
+        .bind(id.as_uuid())
+        .bind(owner_id.as_uuid())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "TodoRepository.restore",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn restore(&self, id: TodoId) -> RepositoryResult<Todo> {
+        tracing::debug!("TodoRepository.restore | {id}");
+
+        let document = sqlx::query_as::<_, TodoDocument>(
+            r#"UPDATE todos
+            SET deleted_at = NULL, updated_at = $1, version = version + 1
+            WHERE id = $2 AND deleted_at IS NOT NULL
+            RETURNING *"#,
+        )
+        .bind(self.clock.now())
+        .bind(id.as_uuid())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "TodoRepository.purge_deleted",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn purge_deleted(&self, deleted_before: OffsetDateTime) -> RepositoryResult<u64> {
+        tracing::debug!("TodoRepository.purge_deleted | {deleted_before}");
+
+        let result = sqlx::query("DELETE FROM todos WHERE deleted_at < $1")
+            .bind(to_utc_primitive(deleted_before))
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(result.rows_affected())
+    }
 }
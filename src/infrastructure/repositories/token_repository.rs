@@ -0,0 +1,173 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use sqlx::{
+    types::{
+        time::{OffsetDateTime, PrimitiveDateTime},
+        Uuid,
+    },
+    FromRow, Pool, Postgres,
+};
+
+use crate::domain::{
+    clock::Clock,
+    entities::token::Token,
+    id_generator::IdGenerator,
+    ids::{TokenId, UserId},
+    repositories::{
+        error::{RepositoryError, RepositoryResult},
+        token_repository::{CreateInput, TokenRepositoryPort},
+    },
+};
+
+use super::{join_scopes, map_sqlx_error, parse_scopes, to_utc_primitive};
+
+#[derive(FromRow, Debug)]
+struct TokenDocument {
+    id: Uuid,
+    user_id: Uuid,
+    name: String,
+    #[allow(dead_code)]
+    token_hash: String,
+    scopes: String,
+    expires_at: Option<PrimitiveDateTime>,
+    last_used_at: Option<PrimitiveDateTime>,
+    created_at: PrimitiveDateTime,
+}
+
+impl From<TokenDocument> for Token {
+    fn from(val: TokenDocument) -> Self {
+        Token {
+            id: val.id.into(),
+            user_id: val.user_id.into(),
+            name: val.name,
+            scopes: parse_scopes(&val.scopes),
+            expires_at: val.expires_at.map(PrimitiveDateTime::assume_utc),
+            last_used_at: val.last_used_at.map(PrimitiveDateTime::assume_utc),
+            created_at: val.created_at.assume_utc(),
+        }
+    }
+}
+
+pub struct TokenRepository {
+    pool: Pool<Postgres>,
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
+}
+
+impl TokenRepository {
+    pub fn new(pool: Pool<Postgres>, clock: Arc<dyn Clock>, ids: Arc<dyn IdGenerator>) -> Self {
+        Self { pool, clock, ids }
+    }
+}
+
+#[async_trait]
+impl TokenRepositoryPort for TokenRepository {
+    #[tracing::instrument(
+        name = "TokenRepository.find_by_hash",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn find_by_hash(&self, token_hash: String) -> RepositoryResult<Token> {
+        tracing::debug!("TokenRepository.find_by_hash");
+
+        let document =
+            sqlx::query_as::<_, TokenDocument>("SELECT * FROM tokens WHERE token_hash = $1")
+                .bind(token_hash)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "TokenRepository.list_by_user",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn list_by_user(&self, user_id: UserId) -> RepositoryResult<Vec<Token>> {
+        tracing::debug!("TokenRepository.list_by_user | {user_id}");
+
+        let documents = sqlx::query_as::<_, TokenDocument>(
+            "SELECT * FROM tokens WHERE user_id = $1 ORDER BY created_at, id",
+        )
+        .bind(user_id.as_uuid())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(documents.into_iter().map(Token::from).collect())
+    }
+
+    #[tracing::instrument(
+        name = "TokenRepository.create",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn create(&self, input: CreateInput) -> RepositoryResult<Token> {
+        tracing::debug!("TokenRepository.create | {}", input.user_id);
+
+        let id = self.ids.generate();
+        let now = to_utc_primitive(self.clock.now());
+
+        let document = sqlx::query_as::<_, TokenDocument>(
+            r#"INSERT INTO tokens
+            (id, user_id, name, token_hash, scopes, expires_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *"#,
+        )
+        .bind(id)
+        .bind(input.user_id.as_uuid())
+        .bind(input.name)
+        .bind(input.token_hash)
+        .bind(join_scopes(&input.scopes))
+        .bind(input.expires_at.map(to_utc_primitive))
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "TokenRepository.touch",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn touch(&self, id: TokenId, used_at: OffsetDateTime) -> RepositoryResult<()> {
+        tracing::debug!("TokenRepository.touch | {id}");
+
+        sqlx::query("UPDATE tokens SET last_used_at = $1 WHERE id = $2")
+            .bind(to_utc_primitive(used_at))
+            .bind(id.as_uuid())
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "TokenRepository.delete",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn delete(&self, user_id: UserId, id: TokenId) -> RepositoryResult<()> {
+        tracing::debug!("TokenRepository.delete | {id}");
+
+        let result = sqlx::query("DELETE FROM tokens WHERE id = $1 AND user_id = $2")
+            .bind(id.as_uuid())
+            .bind(user_id.as_uuid())
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -1,152 +1,272 @@
-use std::str::FromStr;
+use std::{str::FromStr, sync::Arc};
 
 use axum::async_trait;
 use sqlx::{
-    types::{
-        time::{OffsetDateTime, PrimitiveDateTime},
-        Uuid,
-    },
-    Error, FromRow,
+    types::{time::OffsetDateTime, Uuid},
+    Error, FromRow, Pool, Postgres, QueryBuilder,
 };
 
-use crate::{
-    domain::{
-        entities::user::User,
-        repositories::{
-            error::{RepositoryError, RepositoryResult},
-            user_repository::{CreateInput, UpdateInput, UserRepositoryPort},
-        },
+use crate::domain::{
+    clock::Clock,
+    entities::user::{Role, User},
+    id_generator::IdGenerator,
+    ids::UserId,
+    repositories::{
+        error::{RepositoryError, RepositoryResult},
+        user_repository::{CreateInput, UpdateInput, UserRepositoryPort},
     },
-    infrastructure::Database,
+    values::{Email, FirstName},
 };
 
+use super::{like_pattern, map_sqlx_error, to_utc_primitive};
+
 #[derive(FromRow, Debug)]
 struct UserDocument {
     id: Uuid,
     email: String,
     first_name: String,
-    #[allow(dead_code)]
-    created_at: PrimitiveDateTime,
-    #[allow(dead_code)]
-    updated_at: PrimitiveDateTime,
+    password_hash: Option<String>,
+    role: String,
+    version: i64,
+    created_at: OffsetDateTime,
+    updated_at: OffsetDateTime,
 }
 
 impl From<UserDocument> for User {
     fn from(val: UserDocument) -> Self {
         User {
-            id: val.id.to_string(),
+            id: val.id.into(),
             email: val.email,
             first_name: val.first_name,
+            password_hash: val.password_hash,
+            role: Role::from_str(&val.role).unwrap_or_default(),
+            version: val.version,
+            created_at: val.created_at,
+            updated_at: val.updated_at,
         }
     }
 }
 
 pub struct UserRepository {
-    db: Database,
+    pool: Pool<Postgres>,
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
 }
 
 impl UserRepository {
-    pub fn new(db: Database) -> Self {
-        Self { db }
+    pub fn new(pool: Pool<Postgres>, clock: Arc<dyn Clock>, ids: Arc<dyn IdGenerator>) -> Self {
+        Self { pool, clock, ids }
     }
 }
 
 #[async_trait]
 impl UserRepositoryPort for UserRepository {
-    async fn find_by_id(&self, id: String) -> RepositoryResult<User> {
+    #[tracing::instrument(
+        name = "UserRepository.find_by_id",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn find_by_id(&self, id: UserId) -> RepositoryResult<User> {
         tracing::debug!("UserRepository.find_by_id | {id}");
 
-        let document = sqlx::query_as::<_, UserDocument>("SELECT * FROM users WHERE id = $1")
-            .bind(Uuid::from_str(&id).map_err(|_| RepositoryError::InvalidUuid)?)
-            .fetch_one(&self.db.pool())
-            .await
-            .map_err(|e| {
-                tracing::error!("{e}");
-                match e {
-                    Error::RowNotFound => RepositoryError::NotFound,
-                    _ => RepositoryError::Unknown,
-                }
-            })?;
+        let document = sqlx::query_as::<_, UserDocument>(
+            "SELECT * FROM users WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id.as_uuid())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
 
         Ok(document.into())
     }
 
+    #[tracing::instrument(
+        name = "UserRepository.find_by_email",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
     async fn find_by_email(&self, email: String) -> RepositoryResult<Option<User>> {
         tracing::debug!("UserRepository.find_by_email | {email}");
 
-        let document = sqlx::query_as::<_, UserDocument>("SELECT * FROM users WHERE email = $1")
-            .bind(email)
-            .fetch_one(&self.db.pool())
-            .await;
+        let document = sqlx::query_as::<_, UserDocument>(
+            "SELECT * FROM users WHERE LOWER(email) = LOWER($1) AND deleted_at IS NULL",
+        )
+        .bind(email)
+        .fetch_one(&self.pool)
+        .await;
 
         match document {
             Ok(doc) => Ok(Some(doc.into())),
             Err(Error::RowNotFound) => Ok(None),
-            Err(e) => {
-                tracing::error!("{e}");
-                Err(RepositoryError::Unknown)
-            }
+            Err(e) => Err(map_sqlx_error(e)),
         }
     }
 
-    async fn update_one(&self, id: String, input: UpdateInput) -> RepositoryResult<User> {
-        tracing::debug!("UserRepository.update_one | {id} | {input:?}");
+    #[tracing::instrument(
+        name = "UserRepository.list",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn list(&self, search: Option<String>) -> RepositoryResult<Vec<User>> {
+        tracing::debug!("UserRepository.list | {search:?}");
 
-        let user = self.find_by_id(id).await?;
-        let now = OffsetDateTime::now_utc();
-        let now = PrimitiveDateTime::new(now.date(), now.time());
+        let mut builder =
+            QueryBuilder::<Postgres>::new("SELECT * FROM users WHERE deleted_at IS NULL");
+        if let Some(search) = search {
+            let pattern = like_pattern(&search);
+            builder
+                .push(" AND (email ILIKE ")
+                .push_bind(pattern.clone())
+                .push(" OR first_name ILIKE ")
+                .push_bind(pattern)
+                .push(")");
+        }
+        builder.push(" ORDER BY email");
+
+        let documents = builder
+            .build_query_as::<UserDocument>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(documents.into_iter().map(User::from).collect())
+    }
+
+    #[tracing::instrument(
+        name = "UserRepository.update_one",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn update_one(&self, id: UserId, input: UpdateInput) -> RepositoryResult<User> {
+        tracing::debug!("UserRepository.update_one | {id} | {input:?}");
 
         let document = sqlx::query_as::<_, UserDocument>(
             r#"UPDATE users
             SET
-            email = $1,
-            first_name = $2,
-            updated_at = $3
-            WHERE id = $4
+            email = COALESCE($1, email),
+            first_name = COALESCE($2, first_name),
+            updated_at = $3,
+            version = version + 1
+            WHERE id = $4 AND deleted_at IS NULL AND ($5::BIGINT IS NULL OR version = $5)
             RETURNING *"#,
         )
-        .bind(input.email)
-        .bind(input.first_name)
-        .bind(now)
-        .bind(Uuid::from_str(&user.id).map_err(|_| RepositoryError::InvalidUuid)?)
-        .fetch_one(&self.db.pool())
+        .bind(input.email.map(Email::into_inner))
+        .bind(input.first_name.map(FirstName::into_inner))
+        .bind(self.clock.now())
+        .bind(id.as_uuid())
+        .bind(input.version)
+        .fetch_one(&self.pool)
         .await
-        .map_err(|e| {
-            tracing::error!("{e}");
-            match e {
-                Error::RowNotFound => RepositoryError::NotFound,
-                _ => RepositoryError::Unknown,
-            }
-        })?;
+        .map_err(map_sqlx_error);
 
-        Ok(document.into())
+        match document {
+            Ok(document) => Ok(document.into()),
+            Err(RepositoryError::NotFound) if input.version.is_some() => {
+                // Tell a stale version apart from a user that does not exist
+                self.find_by_id(id).await?;
+
+                Err(RepositoryError::VersionMismatch)
+            }
+            Err(e) => Err(e),
+        }
     }
 
+    #[tracing::instrument(
+        name = "UserRepository.create",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
     async fn create(&self, input: CreateInput) -> RepositoryResult<User> {
         tracing::debug!("UserRepository.create | {input:?}");
 
-        let id = Uuid::new_v4();
-        let now = OffsetDateTime::now_utc();
-        let now = PrimitiveDateTime::new(now.date(), now.time());
+        let id = self.ids.generate();
 
         let document = sqlx::query_as::<_, UserDocument>(
             r#"INSERT INTO users
-            (id, email, first_name, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5)
+            (id, email, first_name, password_hash, role, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $6)
             RETURNING *"#,
         )
         .bind(id)
-        .bind(input.email)
-        .bind(input.first_name)
-        .bind(now)
-        .bind(now)
-        .fetch_one(&self.db.pool())
+        .bind(input.email.into_inner())
+        .bind(input.first_name.into_inner())
+        .bind(input.password_hash)
+        .bind(input.role.as_str())
+        .bind(self.clock.now())
+        .fetch_one(&self.pool)
         .await
-        .map_err(|e| {
-            tracing::error!("User Repository Error: {e}");
-            RepositoryError::Unknown
-        })?;
+        .map_err(map_sqlx_error)?;
 
         Ok(document.into())
     }
+
+    #[tracing::instrument(
+        name = "UserRepository.set_role",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn set_role(&self, id: UserId, role: Role) -> RepositoryResult<User> {
+        tracing::debug!("UserRepository.set_role | {id} | {role:?}");
+
+        let document = sqlx::query_as::<_, UserDocument>(
+            r#"UPDATE users
+            SET
+            role = $1,
+            updated_at = $2,
+            version = version + 1
+            WHERE id = $3 AND deleted_at IS NULL
+            RETURNING *"#,
+        )
+        .bind(role.as_str())
+        .bind(self.clock.now())
+        .bind(id.as_uuid())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(document.into())
+    }
+
+    #[tracing::instrument(
+        name = "UserRepository.delete",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn delete(&self, id: UserId) -> RepositoryResult<()> {
+        tracing::debug!("UserRepository.delete | {id}");
+
+        let now = self.clock.now();
+
+        let result =
+            sqlx::query("UPDATE users SET deleted_at = $1, updated_at = $2, version = version + 1 WHERE id = $3 AND deleted_at IS NULL")
+                .bind(to_utc_primitive(now))
+                .bind(now)
+                .bind(id.as_uuid())
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlx_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "UserRepository.purge_deleted",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn purge_deleted(&self, deleted_before: OffsetDateTime) -> RepositoryResult<u64> {
+        tracing::debug!("UserRepository.purge_deleted | {deleted_before}");
+
+        let result = sqlx::query("DELETE FROM users WHERE deleted_at < $1")
+            .bind(to_utc_primitive(deleted_before))
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(result.rows_affected())
+    }
 }
@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+
+use crate::app_state::AppState;
+
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Periodically hard deletes todos and users that have been in the trash for
+/// longer than `retention`.
+pub fn spawn_trash_purge(app_state: AppState, retention: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PURGE_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let deleted_before = app_state.clock.now() - retention;
+            tracing::debug!("Purging trash deleted before {deleted_before}");
+
+            match app_state.todo_service.purge_deleted(deleted_before).await {
+                Ok(purged) => tracing::info!("Purged {purged} todos from trash"),
+                Err(e) => tracing::error!("Failed to purge todos: {e:?}"),
+            }
+
+            match app_state.user_service.purge_deleted(deleted_before).await {
+                Ok(purged) => tracing::info!("Purged {purged} users from trash"),
+                Err(e) => tracing::error!("Failed to purge users: {e:?}"),
+            }
+        }
+    })
+}
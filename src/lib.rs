@@ -1,43 +1,231 @@
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Duration};
 
 use error::ServiceStartupError;
 
-use crate::{adapters::api, app_state::AppState};
+use crate::{
+    adapters::api::{self, cors::CorsConfig},
+    app_state::AppState,
+    infrastructure::Database,
+};
 
-mod adapters;
-mod app_state;
-mod domain;
+pub use crate::{app_state::Storage, shutdown::ShutdownHandle};
+
+pub mod adapters;
+pub mod app_state;
+pub mod config;
+pub mod domain;
 pub mod error;
-mod infrastructure;
-mod services;
+pub mod fixtures;
+pub mod infrastructure;
+mod jobs;
+pub mod services;
+mod shutdown;
+pub mod telemetry;
+
+const DEFAULT_TRASH_RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
 
 pub struct App {
     address: SocketAddr,
-    connection_string: String,
+    storage: Storage,
+    trash_retention: Duration,
+    drain_timeout: Duration,
+    readiness_grace: Duration,
+    metrics_address: Option<SocketAddr>,
+    cors: CorsConfig,
+    secure_cookies: bool,
+    auto_migrate: bool,
+    shutdown: ShutdownHandle,
 }
 
 /// Constructor
 impl App {
-    pub fn new(address: SocketAddr, connection_string: String) -> Self {
+    pub fn new(address: SocketAddr, storage: Storage) -> Self {
         Self {
             address,
-            connection_string,
+            storage,
+            trash_retention: DEFAULT_TRASH_RETENTION,
+            drain_timeout: DEFAULT_DRAIN_TIMEOUT,
+            readiness_grace: Duration::ZERO,
+            metrics_address: None,
+            cors: CorsConfig::default(),
+            secure_cookies: true,
+            auto_migrate: true,
+            shutdown: ShutdownHandle::new(),
         }
     }
+
+    /// How long soft deleted todos and users are kept before being purged.
+    pub fn with_trash_retention(mut self, trash_retention: Duration) -> Self {
+        self.trash_retention = trash_retention;
+        self
+    }
+
+    /// How long in-flight requests may take to finish once shutdown starts,
+    /// after which their connections are dropped.
+    pub fn with_drain_timeout(mut self, drain_timeout: Duration) -> Self {
+        self.drain_timeout = drain_timeout;
+        self
+    }
+
+    /// How long `/readyz` reports 503 once shutdown starts before new
+    /// connections are refused, so load balancers stop routing here first.
+    /// None by default.
+    pub fn with_readiness_grace(mut self, readiness_grace: Duration) -> Self {
+        self.readiness_grace = readiness_grace;
+        self
+    }
+
+    /// Serves `/metrics` on its own address instead of next to the API, so it
+    /// can be kept off the public network.
+    pub fn with_metrics_address(mut self, metrics_address: SocketAddr) -> Self {
+        self.metrics_address = Some(metrics_address);
+        self
+    }
+
+    /// Lets browsers call the API from other origins. None is allowed by
+    /// default.
+    pub fn with_cors(mut self, cors: CorsConfig) -> Self {
+        self.cors = cors;
+        self
+    }
+
+    /// Whether the session cookie is marked `Secure`, i.e. only sent over
+    /// HTTPS. On by default, turn it off to log in over plain HTTP in
+    /// development.
+    pub fn with_secure_cookies(mut self, secure_cookies: bool) -> Self {
+        self.secure_cookies = secure_cookies;
+        self
+    }
+
+    /// Whether pending migrations are applied before serving. On by default.
+    pub fn with_auto_migrate(mut self, auto_migrate: bool) -> Self {
+        self.auto_migrate = auto_migrate;
+        self
+    }
 }
 
 /// Methods
 impl App {
+    /// Stops [`run`](Self::run) like SIGTERM does.
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Serves until SIGINT, SIGTERM or [`ShutdownHandle::shutdown`], then
+    /// drains connections and closes the database pool.
     pub async fn run(&self) -> Result<(), ServiceStartupError> {
         tracing::info!("Starting Server on: {}", self.address);
 
-        let app_state = AppState::new(&self.connection_string).await?;
+        let database = match &self.storage {
+            Storage::Database {
+                connection_string,
+                pool,
+            } => Some(Database::connect(connection_string, pool).await?),
+            Storage::Memory => None,
+        };
+        if let Some(database) = &database {
+            if self.auto_migrate {
+                database.migrate().await?;
+            } else {
+                tracing::info!("Skipping migrations");
+            }
+        }
+        let (clock, ids) = app_state::system_clock_and_ids();
+        let app_state = match &database {
+            Some(database) => AppState::from_database(database.clone(), clock, ids),
+            None => AppState::with_clock_and_ids(&Storage::Memory, clock, ids).await?,
+        };
+        let app_state = AppState {
+            secure_cookies: self.secure_cookies,
+            ..app_state
+        };
+
+        let purge = jobs::spawn_trash_purge(app_state.clone(), self.trash_retention);
+        let signals = tokio::spawn({
+            let shutdown = self.shutdown.clone();
+            async move {
+                shutdown::termination_signal().await;
+                shutdown.shutdown();
+            }
+        });
 
-        axum::Server::bind(&self.address)
-            .serve(api::build_route(app_state)?.into_make_service())
-            .await
-            .map_err(|_| ServiceStartupError::ServiceStartup { addr: self.address })?;
+        let mut router = api::build_route(app_state.clone())?;
+        if let Some(cors) = api::cors::cors_layer(&self.cors) {
+            router = router.layer(cors);
+        }
+        let admin = match self.metrics_address {
+            Some(metrics_address) => {
+                tracing::info!("Serving metrics on: {metrics_address}");
+
+                let shutdown = self.shutdown.clone();
+                let admin = axum::Server::try_bind(&metrics_address)
+                    .map_err(|e| {
+                        tracing::error!("{e}");
+                        ServiceStartupError::ServiceStartup {
+                            addr: metrics_address,
+                        }
+                    })?
+                    .serve(api::build_metrics_route(app_state.clone()).into_make_service())
+                    .with_graceful_shutdown(async move { shutdown.requested().await });
+                Some(tokio::spawn(admin))
+            }
+            None => {
+                router = router.merge(api::build_metrics_route(app_state.clone()));
+                None
+            }
+        };
+
+        let health_service = app_state.health_service.clone();
+        let shutdown = self.shutdown.clone();
+        let readiness_grace = self.readiness_grace;
+        let server = axum::Server::try_bind(&self.address)
+            .map_err(|e| {
+                tracing::error!("{e}");
+                ServiceStartupError::ServiceStartup { addr: self.address }
+            })?
+            .serve(router.into_make_service())
+            .with_graceful_shutdown(async move {
+                shutdown.requested().await;
+                health_service.begin_shutdown();
+                if !readiness_grace.is_zero() {
+                    tracing::info!("Shutting down, reporting not ready for {readiness_grace:?}");
+                    tokio::time::sleep(readiness_grace).await;
+                }
+                tracing::info!("Shutting down, draining connections");
+            });
+        tokio::pin!(server);
+
+        let result = tokio::select! {
+            result = &mut server => result,
+            _ = self.shutdown.requested() => {
+                let deadline = self.readiness_grace + self.drain_timeout;
+                match tokio::time::timeout(deadline, &mut server).await {
+                    Ok(result) => result,
+                    Err(_) => {
+                        tracing::warn!(
+                            "Requests still in flight after {:?}, dropping their connections",
+                            self.drain_timeout
+                        );
+                        Ok(())
+                    }
+                }
+            }
+        };
+
+        signals.abort();
+        purge.abort();
+        if let Some(admin) = admin {
+            admin.abort();
+        }
+        if let Some(database) = database {
+            database.close().await;
+        }
+        tracing::info!("Server stopped");
 
-        Ok(())
+        result.map_err(|e| {
+            tracing::error!("{e}");
+            ServiceStartupError::ServiceStartup { addr: self.address }
+        })
     }
 }
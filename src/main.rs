@@ -1,20 +1,250 @@
-use std::net::{Ipv4Addr, SocketAddr};
+use std::{
+    fs,
+    io::{self, BufRead},
+    path::PathBuf,
+    process::ExitCode,
+    time::Duration,
+};
 
 use clap::Parser;
-use rust_web_server::{error::ServiceStartupError, App};
+use rust_web_server::{
+    adapters::api,
+    app_state::{system_clock_and_ids, AppState},
+    config::{Cli, Command, Config, MigrateCommand, UserCommand},
+    domain::{
+        services::{error::ServiceError, policy::Actor, user_service},
+        values::{Email, FirstName},
+    },
+    error::ServiceStartupError,
+    fixtures::Fixtures,
+    infrastructure::Database,
+    telemetry, App, Storage,
+};
 
-use crate::config::Config;
+#[tokio::main]
+async fn main() -> Result<ExitCode, ServiceStartupError> {
+    let cli = Cli::parse();
+    let command = cli
+        .command
+        .clone()
+        .unwrap_or(Command::Serve { no_migrate: false });
+    if let Command::Openapi = command {
+        println!("{}", api::openapi_json());
+        return Ok(ExitCode::SUCCESS);
+    }
 
-mod config;
+    let config = match Config::load(&cli) {
+        Ok(config) => config,
+        Err(e) => {
+            eprint!("{e}");
+            return Ok(ExitCode::from(2));
+        }
+    };
 
-#[tokio::main]
-async fn main() -> Result<(), ServiceStartupError> {
-    tracing_subscriber::fmt().init();
+    let _telemetry = telemetry::init(
+        config.log_format(),
+        &config.log_level,
+        config.otlp_endpoint.as_deref(),
+    )?;
+
+    match command {
+        Command::Serve { no_migrate } => serve(config, !no_migrate).await,
+        Command::Migrate(command) => migrate(config, command).await,
+        Command::Seed { file } => seed(config, file).await,
+        Command::User(command) => user(config, command).await,
+        Command::Openapi => unreachable!("handled before loading the configuration"),
+    }
+}
+
+async fn serve(config: Config, auto_migrate: bool) -> Result<ExitCode, ServiceStartupError> {
+    let trash_retention = Duration::from_secs(config.trash_retention_days * 24 * 60 * 60);
+
+    let mut app = App::new(config.address(), config.storage())
+        .with_trash_retention(trash_retention)
+        .with_drain_timeout(Duration::from_secs(config.drain_timeout_secs))
+        .with_readiness_grace(Duration::from_secs(config.readiness_grace_secs))
+        .with_cors(config.cors())
+        .with_secure_cookies(config.secure_cookies)
+        .with_auto_migrate(auto_migrate);
+    if let Some(metrics_address) = config.metrics_address() {
+        app = app.with_metrics_address(metrics_address);
+    }
+
+    app.run().await?;
 
-    let config = Config::parse();
-    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, config.port));
+    Ok(ExitCode::SUCCESS)
+}
+
+async fn migrate(config: Config, command: MigrateCommand) -> Result<ExitCode, ServiceStartupError> {
+    let Some(database) = database(&config).await? else {
+        eprintln!("Migrations need database storage");
+        return Ok(ExitCode::FAILURE);
+    };
+
+    match command {
+        MigrateCommand::Up => database.migrate().await?,
+        MigrateCommand::Down { to } => {
+            let target = match to {
+                Some(to) => to,
+                None => {
+                    // The newest applied migration is reverted, down to the
+                    // one before it
+                    let applied: Vec<i64> = database
+                        .migration_status()
+                        .await?
+                        .into_iter()
+                        .filter(|migration| migration.applied)
+                        .map(|migration| migration.version)
+                        .collect();
+                    match applied.as_slice() {
+                        [] => {
+                            println!("No migration to revert");
+                            return Ok(ExitCode::SUCCESS);
+                        }
+                        [.., previous, _] => *previous,
+                        [_] => 0,
+                    }
+                }
+            };
+            database.undo(target).await?;
+        }
+        MigrateCommand::Status => {}
+    }
+
+    for migration in database.migration_status().await? {
+        println!(
+            "{} {:<7} {}",
+            migration.version,
+            if migration.applied {
+                "applied"
+            } else {
+                "pending"
+            },
+            migration.description
+        );
+    }
+    database.close().await;
+
+    Ok(ExitCode::SUCCESS)
+}
 
-    App::new(addr, config.connection_string).run().await?;
+async fn seed(config: Config, file: Option<PathBuf>) -> Result<ExitCode, ServiceStartupError> {
+    let fixtures = match file {
+        Some(file) => {
+            let fixtures = fs::read_to_string(&file)
+                .map_err(|e| e.to_string())
+                .and_then(|json| Fixtures::from_json(&json).map_err(|e| e.to_string()));
+            match fixtures {
+                Ok(fixtures) => fixtures,
+                Err(e) => {
+                    eprintln!("Cannot load fixtures from {}: {e}", file.display());
+                    return Ok(ExitCode::FAILURE);
+                }
+            }
+        }
+        None => Fixtures::demo(),
+    };
+    let app_state = app_state(&config).await?;
+
+    report(fixtures.load(&app_state).await.map(|seeded| {
+        println!(
+            "Created {} users and {} todos, skipped {} existing users",
+            seeded.users, seeded.todos, seeded.skipped_users
+        );
+    }))
+}
+
+async fn user(config: Config, command: UserCommand) -> Result<ExitCode, ServiceStartupError> {
+    let app_state = app_state(&config).await?;
+    let user_service = &app_state.user_service;
+
+    let result = match command {
+        UserCommand::Create {
+            email,
+            first_name,
+            password,
+            role,
+        } => {
+            let password = match password {
+                Some(password) => password,
+                None => {
+                    let mut password = String::new();
+                    if let Err(e) = io::stdin().lock().read_line(&mut password) {
+                        eprintln!("Cannot read the password from stdin: {e}");
+                        return Ok(ExitCode::FAILURE);
+                    }
+                    password.trim_end_matches(['\r', '\n']).to_string()
+                }
+            };
+
+            let created = match (Email::parse(email), FirstName::parse(first_name)) {
+                (Ok(email), Ok(first_name)) => {
+                    user_service
+                        .create(user_service::CreateInput {
+                            email,
+                            first_name,
+                            password,
+                            role: role.into(),
+                        })
+                        .await
+                }
+                (email, first_name) => Err(ServiceError::Validation(
+                    email.err().into_iter().chain(first_name.err()).collect(),
+                )),
+            };
+
+            created.map(|user| println!("{}", user.id))
+        }
+        UserCommand::List => user_service.list(Actor::system(), None).await.map(|users| {
+            for user in users {
+                println!(
+                    "{} {:<6} {} {}",
+                    user.id,
+                    user.role.as_str(),
+                    user.email,
+                    user.first_name
+                );
+            }
+        }),
+        UserCommand::SetRole { id, role } => user_service
+            .set_role(Actor::system(), id, role.into())
+            .await
+            .map(|user| println!("{} is now {}", user.email, user.role.as_str())),
+    };
+
+    report(result)
+}
+
+/// The configured database, connected without migrating.
+async fn database(config: &Config) -> Result<Option<Database>, ServiceStartupError> {
+    match config.storage() {
+        Storage::Database {
+            connection_string,
+            pool,
+        } => Ok(Some(Database::connect(&connection_string, &pool).await?)),
+        Storage::Memory => Ok(None),
+    }
+}
+
+async fn app_state(config: &Config) -> Result<AppState, ServiceStartupError> {
+    match database(config).await? {
+        Some(database) => {
+            let (clock, ids) = system_clock_and_ids();
+            Ok(AppState::from_database(database, clock, ids))
+        }
+        None => {
+            tracing::warn!("Using in-memory storage, changes are lost on exit");
+            Ok(AppState::in_memory())
+        }
+    }
+}
 
-    Ok(())
+fn report(result: Result<(), ServiceError>) -> Result<ExitCode, ServiceStartupError> {
+    match result {
+        Ok(()) => Ok(ExitCode::SUCCESS),
+        Err(e) => {
+            eprintln!("Failed: {e:?}");
+            Ok(ExitCode::FAILURE)
+        }
+    }
 }
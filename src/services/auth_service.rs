@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use time::Duration;
+
+use crate::domain::{
+    clock::Clock,
+    entities::{session::Session, user::User},
+    repositories::{
+        error::RepositoryError,
+        session_repository::{CreateInput as SessionCreateInput, SessionRepositoryPort},
+        user_repository::UserRepositoryPort,
+    },
+    services::{
+        auth_service::{AuthServicePort, LoginInput},
+        error::{ServiceError, ServiceResult},
+    },
+};
+
+use super::password;
+
+const SESSION_TTL: Duration = Duration::days(7);
+
+pub struct AuthService {
+    user_repository: Arc<dyn UserRepositoryPort>,
+    session_repository: Arc<dyn SessionRepositoryPort>,
+    clock: Arc<dyn Clock>,
+}
+
+impl AuthService {
+    pub fn new(
+        user_repository: Arc<dyn UserRepositoryPort>,
+        session_repository: Arc<dyn SessionRepositoryPort>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            user_repository,
+            session_repository,
+            clock,
+        }
+    }
+}
+
+#[async_trait]
+impl AuthServicePort for AuthService {
+    #[tracing::instrument(name = "AuthService.login", skip_all)]
+    async fn login(&self, input: LoginInput) -> ServiceResult<(Session, User)> {
+        tracing::debug!("AuthService.login | {input:?}");
+
+        let user = self.user_repository.find_by_email(input.email).await?;
+
+        let Some((user, password_hash)) = user.and_then(|user| {
+            let password_hash = user.password_hash.clone()?;
+            Some((user, password_hash))
+        }) else {
+            tracing::warn!("Unknown email or user has no password set");
+            password::verify_dummy(input.password).await?;
+            return Err(ServiceError::Unauthorized);
+        };
+
+        if !password::verify(input.password, password_hash).await? {
+            tracing::warn!("Invalid password supplied");
+            return Err(ServiceError::Unauthorized);
+        }
+
+        let session = self
+            .session_repository
+            .create(SessionCreateInput {
+                user_id: user.id,
+                expires_at: self.clock.now() + SESSION_TTL,
+            })
+            .await?;
+
+        Ok((session, user))
+    }
+
+    #[tracing::instrument(name = "AuthService.logout", skip_all)]
+    async fn logout(&self, session_id: String) -> ServiceResult<()> {
+        tracing::debug!("AuthService.logout");
+
+        match self.session_repository.delete(session_id).await {
+            Ok(()) | Err(RepositoryError::InvalidUuid) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[tracing::instrument(name = "AuthService.authenticate", skip_all)]
+    async fn authenticate(&self, session_id: String) -> ServiceResult<User> {
+        tracing::debug!("AuthService.authenticate");
+
+        let session = match self.session_repository.find_by_id(session_id).await {
+            Ok(session) => session,
+            Err(RepositoryError::NotFound | RepositoryError::InvalidUuid) => {
+                return Err(ServiceError::Unauthorized)
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        if session.expires_at <= self.clock.now() {
+            tracing::warn!("Session expired");
+            self.session_repository.delete(session.id).await?;
+
+            return Err(ServiceError::Unauthorized);
+        }
+
+        match self.user_repository.find_by_id(session.user_id).await {
+            Ok(user) => Ok(user),
+            Err(RepositoryError::NotFound) => Err(ServiceError::Unauthorized),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+use axum::async_trait;
+
+use crate::domain::{
+    repositories::health_repository::HealthRepositoryPort,
+    services::{
+        error::{ServiceError, ServiceResult},
+        health_service::{HealthServicePort, Status},
+    },
+};
+
+pub struct HealthService {
+    health_repository: Arc<dyn HealthRepositoryPort>,
+    started_at: Instant,
+    shutting_down: AtomicBool,
+}
+
+impl HealthService {
+    pub fn new(health_repository: Arc<dyn HealthRepositoryPort>) -> Self {
+        Self {
+            health_repository,
+            started_at: Instant::now(),
+            shutting_down: AtomicBool::new(false),
+        }
+    }
+}
+
+#[async_trait]
+impl HealthServicePort for HealthService {
+    async fn readiness(&self) -> ServiceResult<()> {
+        if self.shutting_down.load(Ordering::Relaxed) {
+            tracing::debug!("HealthService.readiness | shutting down");
+            return Err(ServiceError::Unavailable);
+        }
+
+        let storage = self.health_repository.check().await?;
+        if storage.migrations_pending {
+            tracing::warn!("HealthService.readiness | migrations pending");
+            return Err(ServiceError::Unavailable);
+        }
+
+        Ok(())
+    }
+
+    async fn status(&self) -> ServiceResult<Status> {
+        let storage = self.health_repository.check().await?;
+
+        Ok(Status {
+            version: env!("CARGO_PKG_VERSION"),
+            uptime: self.started_at.elapsed(),
+            storage,
+        })
+    }
+
+    fn begin_shutdown(&self) {
+        tracing::info!("HealthService.begin_shutdown");
+
+        self.shutting_down.store(true, Ordering::Relaxed);
+    }
+}
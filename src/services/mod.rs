@@ -1,2 +1,6 @@
+pub mod auth_service;
+pub mod health_service;
+mod password;
 pub mod todo_service;
+pub mod token_service;
 pub mod user_service;
@@ -0,0 +1,88 @@
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+
+use std::sync::OnceLock;
+
+use crate::domain::services::error::{FieldError, ServiceError, ServiceResult};
+
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Hashes a plain text password into a PHC string. Hashing is deliberately
+/// slow, so it runs on the blocking thread pool.
+pub async fn hash(password: String) -> ServiceResult<String> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        tracing::warn!("Password shorter than {MIN_PASSWORD_LENGTH} characters");
+        return Err(ServiceError::Validation(vec![FieldError::new(
+            "password",
+            format!("must be at least {MIN_PASSWORD_LENGTH} characters long"),
+        )]));
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let salt = SaltString::generate(&mut OsRng);
+
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| {
+                tracing::error!("{e}");
+                ServiceError::Unknown
+            })
+    })
+    .await
+    .map_err(|e| {
+        tracing::error!("{e}");
+        ServiceError::Unknown
+    })?
+}
+
+/// Checks a plain text password against a PHC string produced by [`hash`].
+pub async fn verify(password: String, password_hash: String) -> ServiceResult<bool> {
+    tokio::task::spawn_blocking(move || {
+        let parsed_hash = PasswordHash::new(&password_hash).map_err(|e| {
+            tracing::error!("{e}");
+            ServiceError::Unknown
+        })?;
+
+        Ok(Argon2::default()
+            .verify_password(password.as_bytes(), &parsed_hash)
+            .is_ok())
+    })
+    .await
+    .map_err(|e| {
+        tracing::error!("{e}");
+        ServiceError::Unknown
+    })?
+}
+
+/// Runs a verification that always fails, for logins without a stored hash.
+/// They take as long as a wrong password, so response times do not tell which
+/// emails are registered.
+pub async fn verify_dummy(password: String) -> ServiceResult<()> {
+    static DUMMY_HASH: OnceLock<String> = OnceLock::new();
+
+    tokio::task::spawn_blocking(move || {
+        let dummy_hash = DUMMY_HASH.get_or_init(|| {
+            let salt = SaltString::generate(&mut OsRng);
+
+            Argon2::default()
+                .hash_password(b"no password is set", &salt)
+                .expect("hashing a constant password succeeds")
+                .to_string()
+        });
+        let parsed_hash = PasswordHash::new(dummy_hash).map_err(|e| {
+            tracing::error!("{e}");
+            ServiceError::Unknown
+        })?;
+        let _ = Argon2::default().verify_password(password.as_bytes(), &parsed_hash);
+
+        Ok(())
+    })
+    .await
+    .map_err(|e| {
+        tracing::error!("{e}");
+        ServiceError::Unknown
+    })?
+}
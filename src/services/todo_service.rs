@@ -1,53 +1,137 @@
 use std::sync::Arc;
 
 use axum::async_trait;
+use time::OffsetDateTime;
 
 use crate::domain::{
+    clock::Clock,
     entities::todo::Todo,
-    repositories::todo_repository::{
-        CreateInput as RepositoryCreateInput, TodoRepositoryPort,
-        UpdateInput as RepositoryUpdateInput,
+    ids::{TodoId, UserId},
+    repositories::{
+        error::RepositoryError,
+        todo_repository::{
+            CreateInput as RepositoryCreateInput, ListQuery, TodoPage, TodoRepositoryPort,
+            UpdateInput as RepositoryUpdateInput,
+        },
+        user_repository::UserRepositoryPort,
     },
     services::{
-        error::ServiceResult,
-        todo_service::{CreateInput, TodoServicePort, UpdateInput},
+        error::{FieldError, ServiceError, ServiceResult},
+        policy::Actor,
+        todo_service::{CreateInput, ListInput, TodoServicePort, UpdateInput},
     },
 };
 
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 100;
+
 pub struct TodoService {
     todo_repository: Arc<dyn TodoRepositoryPort>,
+    user_repository: Arc<dyn UserRepositoryPort>,
+    clock: Arc<dyn Clock>,
 }
 
 impl TodoService {
-    pub fn new(todo_repository: Arc<dyn TodoRepositoryPort>) -> Self {
-        Self { todo_repository }
+    pub fn new(
+        todo_repository: Arc<dyn TodoRepositoryPort>,
+        user_repository: Arc<dyn UserRepositoryPort>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            todo_repository,
+            user_repository,
+            clock,
+        }
     }
 }
 
 #[async_trait]
 impl TodoServicePort for TodoService {
-    async fn list(&self) -> ServiceResult<Vec<Todo>> {
-        tracing::debug!("TodoService.list");
+    #[tracing::instrument(name = "TodoService.list_for_user", skip_all)]
+    async fn list_for_user(
+        &self,
+        actor: Actor,
+        user_id: UserId,
+        input: ListInput,
+    ) -> ServiceResult<TodoPage> {
+        tracing::debug!("TodoService.list_for_user | {user_id} | {input:?}");
 
-        let todos = self.todo_repository.list().await?;
+        actor.ensure_can_manage(user_id)?;
 
-        Ok(todos)
+        if let Some(cursor) = &input.after {
+            if cursor.sort_by != input.sort_by || cursor.direction != input.direction {
+                tracing::warn!("Cursor was issued for a different sort order");
+                return Err(ServiceError::Validation(vec![FieldError::new(
+                    "cursor",
+                    "was issued for a different sort order",
+                )]));
+            }
+        }
+
+        let user = self.user_repository.find_by_id(user_id).await?;
+
+        let query = ListQuery {
+            owner_id: user.id,
+            completed: input.completed,
+            search: input.search.filter(|search| !search.trim().is_empty()),
+            created_after: input.created_after,
+            created_before: input.created_before,
+            updated_after: input.updated_after,
+            updated_before: input.updated_before,
+            sort_by: input.sort_by,
+            direction: input.direction,
+            after: input.after,
+            limit: input
+                .limit
+                .unwrap_or(DEFAULT_PAGE_SIZE)
+                .clamp(1, MAX_PAGE_SIZE),
+        };
+
+        let page = self.todo_repository.list(query).await?;
+
+        Ok(page)
     }
 
-    async fn get(&self, todo_id: String) -> ServiceResult<Todo> {
+    #[tracing::instrument(name = "TodoService.get", skip_all)]
+    async fn get(&self, actor: Actor, todo_id: TodoId) -> ServiceResult<Todo> {
         tracing::debug!("TodoService.get | {todo_id}");
 
-        let todo = self.todo_repository.find_by_id(todo_id).await?;
+        self.find_managed(&actor, todo_id).await
+    }
+
+    #[tracing::instrument(name = "TodoService.get_for_user", skip_all)]
+    async fn get_for_user(
+        &self,
+        actor: Actor,
+        user_id: UserId,
+        todo_id: TodoId,
+    ) -> ServiceResult<Todo> {
+        tracing::debug!("TodoService.get_for_user | {user_id} | {todo_id}");
+
+        actor.ensure_can_manage(user_id)?;
+
+        let todo = self
+            .todo_repository
+            .find_by_owner_and_id(user_id, todo_id)
+            .await?;
 
         Ok(todo)
     }
 
-    async fn create(&self, input: CreateInput) -> ServiceResult<Todo> {
+    #[tracing::instrument(name = "TodoService.create", skip_all)]
+    async fn create(&self, actor: Actor, input: CreateInput) -> ServiceResult<Todo> {
         tracing::debug!("TodoService.create | {input:?}");
 
+        actor.ensure_can_manage(input.owner_id)?;
+
+        let owner = self.user_repository.find_by_id(input.owner_id).await?;
+
         let input = RepositoryCreateInput {
+            owner_id: owner.id,
             title: input.title,
             description: input.description,
+            priority: input.priority.unwrap_or_default(),
+            due_at: input.due_at,
         };
 
         let todo = self.todo_repository.create(input).await?;
@@ -55,20 +139,159 @@ impl TodoServicePort for TodoService {
         Ok(todo)
     }
 
-    async fn update(&self, id: String, update: UpdateInput) -> ServiceResult<Todo> {
+    #[tracing::instrument(name = "TodoService.update", skip_all)]
+    async fn update(&self, actor: Actor, id: TodoId, update: UpdateInput) -> ServiceResult<Todo> {
         tracing::debug!("TodoService.update | {id} | {update:?}");
 
-        if update.title.is_none() && update.description.is_none() {
-            tracing::warn!("No new information passed into update. Returning early");
-            let todo = self.todo_repository.find_by_id(id).await?;
+        let todo = self.find_managed(&actor, id).await?;
+
+        if update
+            .version
+            .is_some_and(|version| version != todo.version)
+        {
+            tracing::warn!("Todo changed since version {:?}", update.version);
+            return Err(ServiceError::PreconditionFailed);
+        }
 
+        if update.title.is_none()
+            && update.description.is_none()
+            && update.priority.is_none()
+            && update.due_at.is_none()
+        {
+            tracing::warn!("No new information passed into update. Returning early");
             return Ok(todo);
         }
 
         let input = RepositoryUpdateInput {
-            id,
+            id: todo.id,
             title: update.title,
             description: update.description,
+            priority: update.priority,
+            due_at: update.due_at,
+            completed_at: None,
+            version: update.version,
+        };
+
+        let todo = self.todo_repository.update_one(input).await?;
+
+        Ok(todo)
+    }
+
+    #[tracing::instrument(name = "TodoService.complete", skip_all)]
+    async fn complete(&self, actor: Actor, id: TodoId) -> ServiceResult<Todo> {
+        tracing::debug!("TodoService.complete | {id}");
+
+        let todo = self.find_managed(&actor, id).await?;
+
+        if todo.completed_at.is_some() {
+            tracing::warn!("Todo already completed. Returning early");
+            return Ok(todo);
+        }
+
+        self.set_completed_at(todo.id, Some(self.clock.now())).await
+    }
+
+    #[tracing::instrument(name = "TodoService.reopen", skip_all)]
+    async fn reopen(&self, actor: Actor, id: TodoId) -> ServiceResult<Todo> {
+        tracing::debug!("TodoService.reopen | {id}");
+
+        let todo = self.find_managed(&actor, id).await?;
+
+        if todo.completed_at.is_none() {
+            tracing::warn!("Todo is not completed. Returning early");
+            return Ok(todo);
+        }
+
+        self.set_completed_at(todo.id, None).await
+    }
+
+    #[tracing::instrument(name = "TodoService.delete", skip_all)]
+    async fn delete(&self, actor: Actor, id: TodoId) -> ServiceResult<()> {
+        tracing::debug!("TodoService.delete | {id}");
+
+        let todo = self.find_managed(&actor, id).await?;
+        self.todo_repository.delete(todo.id).await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "TodoService.list_trash_for_user", skip_all)]
+    async fn list_trash_for_user(&self, actor: Actor, user_id: UserId) -> ServiceResult<Vec<Todo>> {
+        tracing::debug!("TodoService.list_trash_for_user | {user_id}");
+
+        actor.ensure_can_manage(user_id)?;
+
+        let user = self.user_repository.find_by_id(user_id).await?;
+        let todos = self.todo_repository.list_deleted_by_owner(user.id).await?;
+
+        Ok(todos)
+    }
+
+    #[tracing::instrument(name = "TodoService.restore", skip_all)]
+    async fn restore(&self, actor: Actor, id: TodoId) -> ServiceResult<Todo> {
+        tracing::debug!("TodoService.restore | {id}");
+
+        if !actor.is_admin() {
+            match self
+                .todo_repository
+                .find_deleted_by_owner_and_id(actor.user_id, id)
+                .await
+            {
+                Ok(_) => {}
+                Err(RepositoryError::NotFound) => {
+                    // Either not trashed, or trashed by someone else and then not found
+                    self.find_managed(&actor, id).await?;
+
+                    return Err(ServiceError::Conflict("Todo is not in the trash".into()));
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        match self.todo_repository.restore(id).await {
+            Ok(todo) => Ok(todo),
+            Err(RepositoryError::NotFound) => {
+                // Distinguish a todo that is not in the trash from one that does not exist
+                self.todo_repository.find_by_id(id).await?;
+
+                Err(ServiceError::Conflict("Todo is not in the trash".into()))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[tracing::instrument(name = "TodoService.purge_deleted", skip_all)]
+    async fn purge_deleted(&self, deleted_before: OffsetDateTime) -> ServiceResult<u64> {
+        tracing::debug!("TodoService.purge_deleted | {deleted_before}");
+
+        let purged = self.todo_repository.purge_deleted(deleted_before).await?;
+
+        Ok(purged)
+    }
+}
+
+impl TodoService {
+    /// The todo, provided `actor` may access it.
+    async fn find_managed(&self, actor: &Actor, id: TodoId) -> ServiceResult<Todo> {
+        let todo = self.todo_repository.find_by_id(id).await?;
+        actor.ensure_can_manage(todo.owner_id)?;
+
+        Ok(todo)
+    }
+
+    async fn set_completed_at(
+        &self,
+        id: TodoId,
+        completed_at: Option<OffsetDateTime>,
+    ) -> ServiceResult<Todo> {
+        let input = RepositoryUpdateInput {
+            id,
+            title: None,
+            description: None,
+            priority: None,
+            due_at: None,
+            completed_at: Some(completed_at),
+            version: None,
         };
 
         let todo = self.todo_repository.update_one(input).await?;
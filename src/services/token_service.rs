@@ -0,0 +1,176 @@
+use std::sync::Arc;
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use axum::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use sha2::{Digest, Sha256};
+
+use crate::domain::{
+    clock::Clock,
+    entities::{
+        token::{Scope, Token},
+        user::User,
+    },
+    ids::{TokenId, UserId},
+    repositories::{
+        error::RepositoryError,
+        token_repository::{CreateInput as RepositoryCreateInput, TokenRepositoryPort},
+        user_repository::UserRepositoryPort,
+    },
+    services::{
+        error::{FieldError, ServiceError, ServiceResult},
+        policy::Actor,
+        token_service::{CreateInput, CreatedToken, TokenServicePort},
+    },
+};
+
+/// Makes tokens recognisable, e.g. by secret scanners.
+const TOKEN_PREFIX: &str = "rws_";
+const TOKEN_BYTES: usize = 32;
+
+pub struct TokenService {
+    token_repository: Arc<dyn TokenRepositoryPort>,
+    user_repository: Arc<dyn UserRepositoryPort>,
+    clock: Arc<dyn Clock>,
+}
+
+impl TokenService {
+    pub fn new(
+        token_repository: Arc<dyn TokenRepositoryPort>,
+        user_repository: Arc<dyn UserRepositoryPort>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            token_repository,
+            user_repository,
+            clock,
+        }
+    }
+}
+
+#[async_trait]
+impl TokenServicePort for TokenService {
+    #[tracing::instrument(name = "TokenService.create", skip_all)]
+    async fn create(
+        &self,
+        actor: Actor,
+        user_id: UserId,
+        input: CreateInput,
+    ) -> ServiceResult<CreatedToken> {
+        tracing::debug!("TokenService.create | {user_id} | {input:?}");
+
+        actor.ensure_can_manage(user_id)?;
+
+        let mut errors = Vec::new();
+        if input.name.trim().is_empty() {
+            errors.push(FieldError::new("name", "must not be empty"));
+        }
+        if input.scopes.is_empty() {
+            errors.push(FieldError::new("scopes", "must not be empty"));
+        }
+        if matches!(input.expires_at, Some(expires_at) if expires_at <= self.clock.now()) {
+            errors.push(FieldError::new("expires_at", "must be in the future"));
+        }
+        if !errors.is_empty() {
+            return Err(ServiceError::Validation(errors));
+        }
+
+        let user = self.user_repository.find_by_id(user_id).await?;
+
+        let secret = generate_secret();
+        // Deduplicated, in a stable order
+        let scopes = [Scope::Read, Scope::Write]
+            .into_iter()
+            .filter(|scope| input.scopes.contains(scope))
+            .collect();
+        let token = self
+            .token_repository
+            .create(RepositoryCreateInput {
+                user_id: user.id,
+                name: input.name.trim().to_string(),
+                token_hash: hash_secret(&secret),
+                scopes,
+                expires_at: input.expires_at,
+            })
+            .await?;
+
+        Ok(CreatedToken { token, secret })
+    }
+
+    #[tracing::instrument(name = "TokenService.list", skip_all)]
+    async fn list(&self, actor: Actor, user_id: UserId) -> ServiceResult<Vec<Token>> {
+        tracing::debug!("TokenService.list | {user_id}");
+
+        actor.ensure_can_manage(user_id)?;
+
+        let tokens = self.token_repository.list_by_user(user_id).await?;
+
+        Ok(tokens)
+    }
+
+    #[tracing::instrument(name = "TokenService.delete", skip_all)]
+    async fn delete(&self, actor: Actor, user_id: UserId, id: TokenId) -> ServiceResult<()> {
+        tracing::debug!("TokenService.delete | {user_id} | {id}");
+
+        actor.ensure_can_manage(user_id)?;
+
+        self.token_repository.delete(user_id, id).await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "TokenService.authenticate", skip_all)]
+    async fn authenticate(&self, secret: String) -> ServiceResult<(Token, User)> {
+        tracing::debug!("TokenService.authenticate");
+
+        if !secret.starts_with(TOKEN_PREFIX) {
+            tracing::warn!("Malformed token");
+            return Err(ServiceError::Unauthorized);
+        }
+
+        let token = match self
+            .token_repository
+            .find_by_hash(hash_secret(&secret))
+            .await
+        {
+            Ok(token) => token,
+            Err(RepositoryError::NotFound) => return Err(ServiceError::Unauthorized),
+            Err(e) => return Err(e.into()),
+        };
+
+        let now = self.clock.now();
+        if matches!(token.expires_at, Some(expires_at) if expires_at <= now) {
+            tracing::warn!("Token expired");
+            return Err(ServiceError::Unauthorized);
+        }
+
+        let user = match self.user_repository.find_by_id(token.user_id).await {
+            Ok(user) => user,
+            Err(RepositoryError::NotFound) => return Err(ServiceError::Unauthorized),
+            Err(e) => return Err(e.into()),
+        };
+
+        self.token_repository.touch(token.id, now).await?;
+
+        Ok((
+            Token {
+                last_used_at: Some(now),
+                ..token
+            },
+            user,
+        ))
+    }
+}
+
+fn generate_secret() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+
+    format!("{TOKEN_PREFIX}{}", URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// Tokens are random and long, so a fast unsalted hash is enough and lets
+/// them be looked up by hash.
+fn hash_secret(secret: &str) -> String {
+    format!("{:x}", Sha256::digest(secret.as_bytes()))
+}
@@ -1,19 +1,27 @@
 use std::sync::Arc;
 
 use axum::async_trait;
+use time::OffsetDateTime;
 
 use crate::domain::{
-    entities::user::User,
-    repositories::user_repository::{
-        CreateInput as RepositoryCreateInput, UpdateInput as RepositoryUpdateInput,
-        UserRepositoryPort,
+    entities::user::{Role, User},
+    ids::UserId,
+    repositories::{
+        error::RepositoryError,
+        user_repository::{
+            CreateInput as RepositoryCreateInput, UpdateInput as RepositoryUpdateInput,
+            UserRepositoryPort,
+        },
     },
     services::{
         error::{ServiceError, ServiceResult},
+        policy::Actor,
         user_service::{CreateInput, UpdateInput, UserServicePort},
     },
 };
 
+use super::password;
+
 pub struct UserService {
     user_repository: Arc<dyn UserRepositoryPort>,
 }
@@ -26,7 +34,8 @@ impl UserService {
 
 #[async_trait]
 impl UserServicePort for UserService {
-    async fn get(&self, id: String) -> ServiceResult<User> {
+    #[tracing::instrument(name = "UserService.get", skip_all)]
+    async fn get(&self, id: UserId) -> ServiceResult<User> {
         tracing::debug!("UserService.get | {id}");
 
         let user = self.user_repository.find_by_id(id).await?;
@@ -34,60 +43,116 @@ impl UserServicePort for UserService {
         Ok(user)
     }
 
+    #[tracing::instrument(name = "UserService.list", skip_all)]
+    async fn list(&self, actor: Actor, search: Option<String>) -> ServiceResult<Vec<User>> {
+        tracing::debug!("UserService.list | {search:?}");
+
+        actor.ensure_admin()?;
+
+        let search = search.filter(|search| !search.trim().is_empty());
+        let users = self.user_repository.list(search).await?;
+
+        Ok(users)
+    }
+
+    #[tracing::instrument(name = "UserService.create", skip_all)]
     async fn create(&self, input: CreateInput) -> ServiceResult<User> {
         tracing::debug!("UserService.create | {input:?}");
 
-        if self.is_email_already_in_use(input.email.clone()).await? {
-            tracing::warn!("Email already claimed in system by other user");
-            return Err(ServiceError::BadInput);
-        }
+        let password_hash = password::hash(input.password).await?;
 
         let input = RepositoryCreateInput {
             email: input.email,
             first_name: input.first_name,
+            password_hash,
+            role: input.role,
         };
 
-        let user = self.user_repository.create(input).await?;
+        let user = self
+            .user_repository
+            .create(input)
+            .await
+            .map_err(map_email_conflict)?;
 
         Ok(user)
     }
 
-    async fn update(&self, id: String, update: UpdateInput) -> ServiceResult<User> {
+    #[tracing::instrument(name = "UserService.update", skip_all)]
+    async fn update(&self, actor: Actor, id: UserId, update: UpdateInput) -> ServiceResult<User> {
         tracing::debug!("UserService.update | {id} | {update:?}");
 
+        actor.ensure_can_manage(id)?;
+
         let user = self.user_repository.find_by_id(id).await?;
 
+        if update
+            .version
+            .is_some_and(|version| version != user.version)
+        {
+            tracing::warn!("User changed since version {:?}", update.version);
+            return Err(ServiceError::PreconditionFailed);
+        }
+
         if update.email.is_none() && update.first_name.is_none() {
             tracing::warn!("No new information passed into update. Returning early");
             return Ok(user);
         }
 
-        if let Some(email) = update.email.clone() {
-            if self.is_email_already_in_use(email).await? {
-                tracing::warn!("Email already claimed in system by other user");
-                return Err(ServiceError::BadInput);
-            }
-        }
         let input = RepositoryUpdateInput {
-            email: update.email.unwrap_or(user.email),
-            first_name: update.first_name.unwrap_or(user.first_name),
+            email: update.email,
+            first_name: update.first_name,
+            version: update.version,
         };
 
-        let user = self.user_repository.update_one(user.id, input).await?;
+        let user = self
+            .user_repository
+            .update_one(user.id, input)
+            .await
+            .map_err(map_email_conflict)?;
 
         Ok(user)
     }
-}
 
-impl UserService {
-    async fn is_email_already_in_use(&self, email: String) -> ServiceResult<bool> {
-        tracing::debug!("UserService.is_email_already_in_use | {email}");
+    #[tracing::instrument(name = "UserService.set_role", skip_all)]
+    async fn set_role(&self, actor: Actor, id: UserId, role: Role) -> ServiceResult<User> {
+        tracing::debug!("UserService.set_role | {id} | {role:?}");
+
+        actor.ensure_admin()?;
 
-        let existing_user = self.user_repository.find_by_email(email).await?;
+        let user = self.user_repository.set_role(id, role).await?;
 
-        match existing_user {
-            Some(_) => Ok(true),
-            None => Ok(false),
+        Ok(user)
+    }
+
+    #[tracing::instrument(name = "UserService.delete", skip_all)]
+    async fn delete(&self, actor: Actor, id: UserId) -> ServiceResult<()> {
+        tracing::debug!("UserService.delete | {id}");
+
+        actor.ensure_can_manage(id)?;
+
+        self.user_repository.delete(id).await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "UserService.purge_deleted", skip_all)]
+    async fn purge_deleted(&self, deleted_before: OffsetDateTime) -> ServiceResult<u64> {
+        tracing::debug!("UserService.purge_deleted | {deleted_before}");
+
+        let purged = self.user_repository.purge_deleted(deleted_before).await?;
+
+        Ok(purged)
+    }
+}
+
+/// The unique index on emails is the only reliable check, a lookup before
+/// writing would race with concurrent registrations.
+fn map_email_conflict(error: RepositoryError) -> ServiceError {
+    match error {
+        RepositoryError::Conflict { field } if field == "email" => {
+            tracing::warn!("Email already claimed in system by other user");
+            ServiceError::EmailTaken
         }
+        e => e.into(),
     }
 }
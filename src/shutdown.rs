@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+/// Stops a running [`App`](crate::App) gracefully: readiness starts failing,
+/// new connections are refused and in-flight requests get to finish. Clones
+/// stop the same app.
+#[derive(Clone)]
+pub struct ShutdownHandle {
+    sender: Arc<watch::Sender<bool>>,
+}
+
+impl ShutdownHandle {
+    pub(crate) fn new() -> Self {
+        let (sender, _) = watch::channel(false);
+
+        Self {
+            sender: Arc::new(sender),
+        }
+    }
+
+    pub fn shutdown(&self) {
+        self.sender.send_replace(true);
+    }
+
+    /// Resolves once [`shutdown`](Self::shutdown) has been called, including
+    /// before this future was created.
+    pub(crate) async fn requested(&self) {
+        let mut receiver = self.sender.subscribe();
+
+        while !*receiver.borrow_and_update() {
+            if receiver.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Resolves on SIGINT, or SIGTERM on unix.
+pub(crate) async fn termination_signal() {
+    let interrupt = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for SIGINT: {e}");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                terminate.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {e}");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = interrupt => tracing::info!("Received SIGINT"),
+        _ = terminate => tracing::info!("Received SIGTERM"),
+    }
+}
@@ -0,0 +1,110 @@
+use std::io;
+
+use opentelemetry::{trace::TracerProvider as _, KeyValue};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{
+    runtime,
+    trace::{self, Tracer, TracerProvider},
+    Resource,
+};
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::{
+    fmt, layer::SubscriberExt, registry::LookupSpan, util::SubscriberInitExt, EnvFilter, Layer,
+};
+
+use crate::error::ServiceStartupError;
+
+const SERVICE_NAME: &str = env!("CARGO_PKG_NAME");
+
+#[derive(Debug, Clone, Copy, Default)]
+pub enum LogFormat {
+    /// Human readable, one line per event.
+    #[default]
+    Pretty,
+    /// One JSON object per event, including the fields of the enclosing
+    /// spans such as the request id.
+    Json,
+}
+
+/// Keeps the trace exporter alive. Dropping it flushes the spans not yet
+/// exported, so hold on to it until the server has stopped.
+#[must_use]
+pub struct Telemetry {
+    tracer_provider: Option<TracerProvider>,
+}
+
+/// Installs the global tracing subscriber. What gets logged is filtered by
+/// `RUST_LOG` directives, e.g. `info,sqlx=warn,rust_web_server=debug`, and
+/// by `log_level` when `RUST_LOG` is not set.
+///
+/// With an `otlp_endpoint`, spans are also exported to that OpenTelemetry
+/// collector over OTLP/HTTP, e.g. `http://localhost:4318`.
+pub fn init(
+    format: LogFormat,
+    log_level: &str,
+    otlp_endpoint: Option<&str>,
+) -> Result<Telemetry, ServiceStartupError> {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(log_level));
+    // Stdout is left to the output of commands such as `user list`
+    let fmt_layer = fmt::layer().with_writer(io::stderr);
+    let fmt_layer = match format {
+        LogFormat::Pretty => fmt_layer.boxed(),
+        LogFormat::Json => fmt_layer
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .boxed(),
+    };
+    let tracer_provider = otlp_endpoint.map(otlp_tracer_provider).transpose()?;
+    let otlp_layer = tracer_provider.as_ref().map(otlp_layer);
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt_layer)
+        .with(otlp_layer)
+        .init();
+
+    Ok(Telemetry { tracer_provider })
+}
+
+/// Batches finished spans and sends them to the collector at `endpoint`.
+/// Must be called from within a Tokio runtime.
+pub fn otlp_tracer_provider(endpoint: &str) -> Result<TracerProvider, ServiceStartupError> {
+    let exporter = opentelemetry_otlp::new_exporter()
+        .http()
+        .with_endpoint(endpoint)
+        .build_span_exporter()
+        .map_err(|e| {
+            eprintln!("Invalid OTLP exporter configuration: {e}");
+            ServiceStartupError::Telemetry
+        })?;
+
+    Ok(TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_config(
+            trace::config()
+                .with_resource(Resource::new([KeyValue::new("service.name", SERVICE_NAME)])),
+        )
+        .build())
+}
+
+/// Turns tracing spans into OpenTelemetry spans handed to `tracer_provider`.
+pub fn otlp_layer<S>(tracer_provider: &TracerProvider) -> OpenTelemetryLayer<S, Tracer>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer(SERVICE_NAME))
+}
+
+impl Drop for Telemetry {
+    fn drop(&mut self) {
+        if let Some(tracer_provider) = &self.tracer_provider {
+            for result in tracer_provider.force_flush() {
+                if let Err(e) = result {
+                    eprintln!("Failed to export spans: {e}");
+                }
+            }
+        }
+    }
+}
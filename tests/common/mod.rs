@@ -0,0 +1,273 @@
+//! Shared harness for the HTTP integration tests. Requests are served
+//! in-process by the router from `build_route` merged with `/metrics`, like
+//! `App` does by default, no socket or database needed.
+
+#![allow(dead_code)]
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, HeaderMap, Method, Request, StatusCode},
+    Router,
+};
+use rust_web_server::{
+    adapters::api::{
+        build_metrics_route, build_route,
+        cors::{cors_layer, CorsConfig},
+    },
+    app_state::{system_clock_and_ids, AppState},
+    domain::{
+        clock::Clock, entities::user::Role, id_generator::IdGenerator, services::policy::Actor,
+    },
+    infrastructure::PoolConfig,
+    Storage,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+pub const PASSWORD: &str = "correct horse battery";
+
+pub struct TestApp {
+    router: Router,
+    app_state: AppState,
+}
+
+pub struct TestResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Value,
+}
+
+impl TestResponse {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+}
+
+/// A registered user and the cookie of their logged in session.
+pub struct TestUser {
+    pub id: String,
+    pub email: String,
+    pub cookie: String,
+}
+
+impl TestApp {
+    /// An app backed by a fresh in-memory store.
+    pub fn new() -> Self {
+        Self::with_state(AppState::in_memory())
+    }
+
+    /// An in-memory app reading the time from `clock` and taking primary keys
+    /// from `ids`.
+    pub fn with_clock_and_ids(clock: Arc<dyn Clock>, ids: Arc<dyn IdGenerator>) -> Self {
+        Self::with_state(AppState::in_memory_with_clock_and_ids(clock, ids))
+    }
+
+    /// An app backed by a fresh, migrated SQLite database in memory.
+    pub async fn sqlite() -> Self {
+        let (clock, ids) = system_clock_and_ids();
+
+        Self::sqlite_with_clock_and_ids(clock, ids).await
+    }
+
+    /// A SQLite app reading the time from `clock` and taking primary keys
+    /// from `ids`.
+    pub async fn sqlite_with_clock_and_ids(
+        clock: Arc<dyn Clock>,
+        ids: Arc<dyn IdGenerator>,
+    ) -> Self {
+        let storage = Storage::Database {
+            connection_string: "sqlite::memory:".to_string(),
+            pool: PoolConfig::default(),
+        };
+        let app_state = AppState::with_clock_and_ids(&storage, clock, ids)
+            .await
+            .expect("sqlite database should open");
+
+        Self::with_state(app_state)
+    }
+
+    pub fn with_state(app_state: AppState) -> Self {
+        let router = build_route(app_state.clone())
+            .expect("router should build")
+            .merge(build_metrics_route(app_state.clone()));
+
+        Self { router, app_state }
+    }
+
+    /// An in-memory app answering cross-origin requests like `App` does with
+    /// `cors`.
+    pub fn with_cors(cors: &CorsConfig) -> Self {
+        let mut app = Self::new();
+        if let Some(layer) = cors_layer(cors) {
+            app.router = app.router.layer(layer);
+        }
+
+        app
+    }
+
+    pub async fn request(
+        &self,
+        method: Method,
+        uri: &str,
+        body: Option<Value>,
+        cookie: Option<&str>,
+    ) -> TestResponse {
+        self.request_with_headers(method, uri, body, cookie, &[])
+            .await
+    }
+
+    /// Like [`Self::request`], with extra headers such as `If-Match`.
+    pub async fn request_with_headers(
+        &self,
+        method: Method,
+        uri: &str,
+        body: Option<Value>,
+        cookie: Option<&str>,
+        headers: &[(&str, &str)],
+    ) -> TestResponse {
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some(cookie) = cookie {
+            request = request.header(header::COOKIE, cookie);
+        }
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+
+        self.send(json_request(request, body)).await
+    }
+
+    /// Like [`Self::request`], authenticated with a personal access token
+    /// instead of a session.
+    pub async fn request_with_token(
+        &self,
+        method: Method,
+        uri: &str,
+        body: Option<Value>,
+        token: &str,
+    ) -> TestResponse {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {token}"));
+
+        self.send(json_request(request, body)).await
+    }
+
+    /// Sends a request built by the test, e.g. to set extra headers.
+    pub async fn send(&self, request: Request<Body>) -> TestResponse {
+        let response = self
+            .router
+            .clone()
+            .oneshot(request)
+            .await
+            .expect("router is infallible");
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let bytes = hyper::body::to_bytes(response.into_body())
+            .await
+            .expect("body should be readable");
+        let body = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes)
+                .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).into_owned()))
+        };
+
+        TestResponse {
+            status,
+            headers,
+            body,
+        }
+    }
+
+    pub async fn get(&self, uri: &str, cookie: Option<&str>) -> TestResponse {
+        self.request(Method::GET, uri, None, cookie).await
+    }
+
+    pub async fn post(&self, uri: &str, body: Value, cookie: Option<&str>) -> TestResponse {
+        self.request(Method::POST, uri, Some(body), cookie).await
+    }
+
+    pub async fn patch(&self, uri: &str, body: Value, cookie: Option<&str>) -> TestResponse {
+        self.request(Method::PATCH, uri, Some(body), cookie).await
+    }
+
+    pub async fn delete(&self, uri: &str, cookie: Option<&str>) -> TestResponse {
+        self.request(Method::DELETE, uri, None, cookie).await
+    }
+
+    /// Creates a personal access token for `user` and returns its secret.
+    pub async fn token(&self, user: &TestUser, scopes: &[&str]) -> String {
+        let response = self
+            .post(
+                &format!("/user/{}/tokens", user.id),
+                serde_json::json!({ "name": "test", "scopes": scopes }),
+                Some(&user.cookie),
+            )
+            .await;
+        assert_eq!(response.status, StatusCode::OK, "{:?}", response.body);
+
+        response.body["token"].as_str().unwrap().to_string()
+    }
+
+    /// Registers a user named `name`, promotes them to admin and logs them
+    /// in.
+    pub async fn admin(&self, name: &str) -> TestUser {
+        let admin = self.user(name).await;
+        self.app_state
+            .user_service
+            .set_role(
+                Actor::system(),
+                admin.id.parse().expect("user id should be a UUID"),
+                Role::Admin,
+            )
+            .await
+            .expect("user should be promoted");
+
+        admin
+    }
+
+    /// Registers a user named `name` and logs them in.
+    pub async fn user(&self, name: &str) -> TestUser {
+        let email = format!("{name}@example.com");
+
+        let response = self
+            .post(
+                "/user",
+                serde_json::json!({ "email": email, "first_name": name, "password": PASSWORD }),
+                None,
+            )
+            .await;
+        assert_eq!(response.status, StatusCode::OK, "{:?}", response.body);
+        let id = response.body["id"].as_str().unwrap().to_string();
+
+        let response = self
+            .post(
+                "/auth/login",
+                serde_json::json!({ "email": email, "password": PASSWORD }),
+                None,
+            )
+            .await;
+        assert_eq!(response.status, StatusCode::OK, "{:?}", response.body);
+        let cookie = response
+            .header("set-cookie")
+            .and_then(|cookie| cookie.split(';').next())
+            .expect("login should set a session cookie")
+            .to_string();
+
+        TestUser { id, email, cookie }
+    }
+}
+
+fn json_request(request: axum::http::request::Builder, body: Option<Value>) -> Request<Body> {
+    match body {
+        Some(body) => request
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    }
+    .expect("request should build")
+}
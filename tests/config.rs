@@ -0,0 +1,134 @@
+// `Jail` closures return the large `figment::Error`
+#![allow(clippy::result_large_err)]
+
+use std::net::{IpAddr, Ipv4Addr};
+
+use clap::Parser;
+use figment::Jail;
+use rust_web_server::{
+    adapters::api::cors::CorsOrigins,
+    config::{Cli, Config, LogFormat},
+    Storage,
+};
+
+fn load(args: &[&str]) -> Result<Config, String> {
+    let cli = Cli::try_parse_from(["rust-web-server"].iter().chain(args)).unwrap();
+    Config::load(&cli).map_err(|e| e.to_string())
+}
+
+#[test]
+fn defaults_are_used_when_nothing_is_set() {
+    Jail::expect_with(|_| {
+        let config = load(&[]).unwrap();
+
+        assert_eq!(config.address().to_string(), "127.0.0.1:8000");
+        assert_eq!(config.log_level, "info");
+        assert_eq!(config.log_format, LogFormat::Pretty);
+        assert!(config.cors_allowed_origins.is_empty());
+        assert_eq!(
+            config.cors_allowed_methods,
+            ["GET", "POST", "PATCH", "DELETE"]
+        );
+        assert!(!config.cors_allow_credentials);
+        assert!(config.secure_cookies);
+        assert_eq!(config.readiness_grace_secs, 0);
+        assert!(config.metrics_address().is_none());
+        match config.storage() {
+            Storage::Database { pool, .. } => assert_eq!(pool.max_connections, 5),
+            Storage::Memory => panic!("database storage is the default"),
+        }
+
+        Ok(())
+    });
+}
+
+#[test]
+fn file_then_environment_then_flags_take_precedence() {
+    Jail::expect_with(|jail| {
+        jail.create_file(
+            "app.toml",
+            r#"
+                host = "0.0.0.0"
+                port = 8001
+                pool_max_connections = 8
+                log_level = "debug"
+                cors_allowed_origins = ["https://app.example.com"]
+            "#,
+        )?;
+        jail.set_env("APP_CONFIG", "app.toml");
+        jail.set_env("APP_PORT", "8002");
+        jail.set_env("APP_POOL_MAX_CONNECTIONS", "9");
+
+        let config = load(&["--port", "8003", "--log-format", "json"]).unwrap();
+
+        assert_eq!(config.host, IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+        assert_eq!(config.port, 8003);
+        assert_eq!(config.pool_max_connections, 9);
+        assert_eq!(config.log_level, "debug");
+        assert_eq!(config.log_format, LogFormat::Json);
+        assert_eq!(config.cors_allowed_origins, ["https://app.example.com"]);
+
+        Ok(())
+    });
+}
+
+#[test]
+fn every_invalid_setting_is_reported() {
+    Jail::expect_with(|jail| {
+        jail.set_env("APP_POOL_MIN_CONNECTIONS", "10");
+        jail.set_env("APP_LOG_LEVEL", "info,=[");
+        jail.set_env("APP_TRASH_RETENTION_DAYS", u64::MAX.to_string());
+
+        let error = load(&[
+            "--connection-string",
+            "mysql://localhost",
+            "--cors-allowed-origin",
+            "https://app.example.com/path",
+            "--cors-allowed-method",
+            "PACTH",
+        ])
+        .unwrap_err();
+
+        assert!(error.contains("connection_string"), "{error}");
+        assert!(error.contains("pool_min_connections (10)"), "{error}");
+        assert!(error.contains("log_level"), "{error}");
+        assert!(error.contains("https://app.example.com/path"), "{error}");
+        assert!(error.contains("'PACTH' is not a method"), "{error}");
+        assert!(error.contains("trash_retention_days"), "{error}");
+
+        Ok(())
+    });
+}
+
+#[test]
+fn any_origin_cannot_carry_credentials() {
+    Jail::expect_with(|_| {
+        let error = load(&[
+            "--cors-allowed-origin",
+            "*",
+            "--cors-allow-credentials",
+            "true",
+        ])
+        .unwrap_err();
+        assert!(error.contains("cors_allow_credentials"), "{error}");
+
+        let config = load(&["--cors-allowed-origin", "*"]).unwrap();
+        assert_eq!(config.cors().allowed_origins, CorsOrigins::Any);
+
+        Ok(())
+    });
+}
+
+#[test]
+fn malformed_sources_are_reported() {
+    Jail::expect_with(|jail| {
+        let error = load(&["--config", "missing.toml"]).unwrap_err();
+        assert!(error.contains("missing.toml does not exist"), "{error}");
+
+        jail.set_env("APP_PORT", "eighty");
+        let error = load(&[]).unwrap_err();
+        assert!(error.contains("\"eighty\", expected u16"), "{error}");
+
+        Ok(())
+    });
+}
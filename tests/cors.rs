@@ -0,0 +1,94 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    http::{header, HeaderValue, Method, Request, StatusCode},
+};
+use common::{TestApp, TestResponse};
+use rust_web_server::adapters::api::cors::{CorsConfig, CorsOrigins};
+
+const ORIGIN: &str = "https://app.example.com";
+
+async fn preflight(app: &TestApp, uri: &str, origin: &str) -> TestResponse {
+    let request = Request::builder()
+        .method(Method::OPTIONS)
+        .uri(uri)
+        .header(header::ORIGIN, origin)
+        .header(header::ACCESS_CONTROL_REQUEST_METHOD, "PATCH")
+        .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "content-type")
+        .body(Body::empty())
+        .unwrap();
+
+    app.send(request).await
+}
+
+#[tokio::test]
+async fn patch_routes_answer_preflight_from_allowed_origin() {
+    let app = TestApp::with_cors(&CorsConfig {
+        allowed_origins: CorsOrigins::List(vec![HeaderValue::from_static(ORIGIN)]),
+        allow_credentials: true,
+        max_age: Duration::from_secs(300),
+        ..CorsConfig::default()
+    });
+
+    for uri in ["/todo/1", "/user/1"] {
+        let response = preflight(&app, uri, ORIGIN).await;
+
+        assert_eq!(response.status, StatusCode::OK, "{uri}");
+        assert_eq!(response.header("access-control-allow-origin"), Some(ORIGIN));
+        assert!(response
+            .header("access-control-allow-methods")
+            .unwrap()
+            .contains("PATCH"));
+        assert!(response
+            .header("access-control-allow-headers")
+            .unwrap()
+            .contains("content-type"));
+        assert_eq!(
+            response.header("access-control-allow-credentials"),
+            Some("true")
+        );
+        assert_eq!(response.header("access-control-max-age"), Some("300"));
+    }
+
+    let response = preflight(&app, "/todo/1", "https://evil.example.com").await;
+    assert_eq!(response.header("access-control-allow-origin"), None);
+}
+
+#[tokio::test]
+async fn any_origin_is_answered_with_a_wildcard() {
+    let app = TestApp::with_cors(&CorsConfig {
+        allowed_origins: CorsOrigins::Any,
+        ..CorsConfig::default()
+    });
+
+    let response = preflight(&app, "/todo/1", ORIGIN).await;
+
+    assert_eq!(response.header("access-control-allow-origin"), Some("*"));
+    assert_eq!(response.header("access-control-allow-credentials"), None);
+
+    let request = Request::builder()
+        .uri("/todo/1")
+        .header(header::ORIGIN, ORIGIN)
+        .body(Body::empty())
+        .unwrap();
+    let response = app.send(request).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+    assert_eq!(response.header("access-control-allow-origin"), Some("*"));
+    assert!(response
+        .header("access-control-expose-headers")
+        .unwrap()
+        .contains("x-request-id"));
+}
+
+#[tokio::test]
+async fn cross_origin_requests_are_not_allowed_by_default() {
+    let app = TestApp::with_cors(&CorsConfig::default());
+
+    let response = preflight(&app, "/todo/1", ORIGIN).await;
+
+    assert_eq!(response.header("access-control-allow-origin"), None);
+    assert_eq!(response.header("access-control-allow-methods"), None);
+}
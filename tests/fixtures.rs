@@ -0,0 +1,93 @@
+mod common;
+
+use axum::http::StatusCode;
+use rust_web_server::{
+    app_state::AppState,
+    domain::{
+        entities::user::Role,
+        services::{error::ServiceError, policy::Actor},
+    },
+    fixtures::{Fixtures, Seeded},
+};
+use serde_json::json;
+
+use common::TestApp;
+
+#[tokio::test]
+async fn demo_fixtures_are_loaded_once() {
+    let app_state = AppState::in_memory();
+
+    let seeded = Fixtures::demo().load(&app_state).await.unwrap();
+    assert_eq!(
+        seeded,
+        Seeded {
+            users: 3,
+            todos: 4,
+            skipped_users: 0
+        }
+    );
+    let seeded = Fixtures::demo().load(&app_state).await.unwrap();
+    assert_eq!(
+        seeded,
+        Seeded {
+            users: 0,
+            todos: 0,
+            skipped_users: 3
+        }
+    );
+
+    let users = app_state
+        .user_service
+        .list(Actor::system(), None)
+        .await
+        .unwrap();
+    let roles: Vec<(&str, Role)> = users
+        .iter()
+        .map(|user| (user.email.as_str(), user.role))
+        .collect();
+    assert_eq!(
+        roles,
+        [
+            ("ada@example.com", Role::Member),
+            ("admin@example.com", Role::Admin),
+            ("grace@example.com", Role::Member),
+        ]
+    );
+    // Roles are written with the user, not by a second update.
+    assert!(users.iter().all(|user| user.version == 1));
+
+    let app = TestApp::with_state(app_state);
+    let response = app
+        .post(
+            "/auth/login",
+            json!({ "email": "ada@example.com", "password": "correct horse battery" }),
+            None,
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    let cookie = response
+        .header("set-cookie")
+        .unwrap()
+        .split(';')
+        .next()
+        .unwrap();
+    let response = app.get("/todo?completed=false", Some(cookie)).await;
+    assert_eq!(response.body["items"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn fixtures_with_an_unknown_priority_are_rejected() {
+    let fixtures = Fixtures::from_json(
+        r#"{ "users": [{
+            "email": "ada@example.com",
+            "first_name": "Ada",
+            "password": "correct horse battery",
+            "todos": [{ "title": "milk", "priority": "urgent" }]
+        }] }"#,
+    )
+    .unwrap();
+
+    let result = fixtures.load(&AppState::in_memory()).await;
+
+    assert!(matches!(result, Err(ServiceError::BadInput(_))));
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::TestApp;
+
+async fn scrape(app: &TestApp) -> String {
+    let response = app.get("/metrics", None).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(
+        response.header("content-type"),
+        Some("text/plain; version=0.0.4")
+    );
+
+    response.body.as_str().unwrap_or_default().to_owned()
+}
+
+/// Value of the sample named exactly `series`, labels included.
+fn sample(metrics: &str, series: &str) -> Option<f64> {
+    metrics
+        .lines()
+        .find_map(|line| line.strip_prefix(series)?.strip_prefix(' '))
+        .map(|value| value.parse().unwrap())
+}
+
+#[tokio::test]
+async fn requests_are_counted_per_route_template_and_status_class() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    for id in ["a", "b"] {
+        app.get(&format!("/todo/{id}"), Some(&user.cookie)).await;
+    }
+    app.get("/todo/c", None).await;
+    app.get("/nowhere", None).await;
+
+    let metrics = scrape(&app).await;
+
+    assert_eq!(
+        sample(
+            &metrics,
+            r#"http_requests_total{method="GET",route="/todo/:id",status="4xx"}"#
+        ),
+        Some(3.0)
+    );
+    assert_eq!(
+        sample(
+            &metrics,
+            r#"http_requests_total{method="POST",route="/auth/login",status="2xx"}"#
+        ),
+        Some(1.0)
+    );
+    assert_eq!(
+        sample(
+            &metrics,
+            r#"http_requests_total{method="GET",route="unmatched",status="4xx"}"#
+        ),
+        Some(1.0)
+    );
+    assert_eq!(
+        sample(
+            &metrics,
+            r#"http_request_duration_seconds_count{method="GET",route="/todo/:id"}"#
+        ),
+        Some(3.0)
+    );
+}
+
+#[tokio::test]
+async fn repository_calls_are_timed_by_outcome() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    app.post(
+        "/todo",
+        json!({ "title": "milk", "description": "" }),
+        Some(&user.cookie),
+    )
+    .await;
+    app.get(
+        "/todo/00000000-0000-0000-0000-000000000000",
+        Some(&user.cookie),
+    )
+    .await;
+
+    let metrics = scrape(&app).await;
+
+    assert_eq!(
+        sample(
+            &metrics,
+            r#"repository_call_duration_seconds_count{method="create",outcome="ok",repository="todo"}"#
+        ),
+        Some(1.0)
+    );
+    assert_eq!(
+        sample(
+            &metrics,
+            r#"repository_call_duration_seconds_count{method="find_by_id",outcome="error",repository="todo"}"#
+        ),
+        Some(1.0)
+    );
+    assert_eq!(
+        sample(
+            &metrics,
+            r#"repository_call_duration_seconds_count{method="create",outcome="ok",repository="user"}"#
+        ),
+        Some(1.0)
+    );
+}
+
+#[tokio::test]
+async fn database_pool_is_reported() {
+    let app = TestApp::sqlite().await;
+
+    let metrics = scrape(&app).await;
+
+    let idle = sample(&metrics, r#"db_pool_connections{state="idle"}"#).unwrap();
+    let in_use = sample(&metrics, r#"db_pool_connections{state="in_use"}"#).unwrap();
+    assert!(idle + in_use >= 1.0);
+}
+
+#[tokio::test]
+async fn in_memory_storage_has_no_pool() {
+    let app = TestApp::new();
+
+    let metrics = scrape(&app).await;
+
+    assert!(!metrics.contains("db_pool_connections{"));
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use axum::http::StatusCode;
+use rust_web_server::{
+    app_state::{system_clock_and_ids, AppState},
+    infrastructure::{Database, PoolConfig},
+};
+
+use common::TestApp;
+
+fn applied(status: &[rust_web_server::infrastructure::MigrationStatus]) -> Vec<bool> {
+    status.iter().map(|migration| migration.applied).collect()
+}
+
+#[tokio::test]
+async fn migrations_are_applied_and_reverted_on_demand() {
+    let database = Database::connect("sqlite::memory:", &PoolConfig::default())
+        .await
+        .unwrap();
+
+    let status = database.migration_status().await.unwrap();
+    assert!(status.len() >= 2);
+    assert!(status
+        .windows(2)
+        .all(|pair| pair[0].version < pair[1].version));
+    assert!(applied(&status).iter().all(|applied| !applied));
+
+    database.migrate().await.unwrap();
+    let status = database.migration_status().await.unwrap();
+    assert!(applied(&status).iter().all(|applied| *applied));
+
+    let previous = status[status.len() - 2].version;
+    database.undo(previous).await.unwrap();
+    let status = database.migration_status().await.unwrap();
+    assert_eq!(applied(&status).last(), Some(&false));
+    assert!(applied(&status[..status.len() - 1])
+        .iter()
+        .all(|applied| *applied));
+}
+
+#[tokio::test]
+async fn not_ready_until_migrations_are_applied() {
+    let database = Database::connect("sqlite::memory:", &PoolConfig::default())
+        .await
+        .unwrap();
+    let (clock, ids) = system_clock_and_ids();
+    let app = TestApp::with_state(AppState::from_database(database.clone(), clock, ids));
+
+    let response = app.get("/readyz", None).await;
+    assert_eq!(response.status, StatusCode::SERVICE_UNAVAILABLE);
+
+    database.migrate().await.unwrap();
+
+    let response = app.get("/readyz", None).await;
+    assert_eq!(response.status, StatusCode::OK);
+}
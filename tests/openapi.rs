@@ -0,0 +1,95 @@
+mod common;
+
+use std::collections::HashSet;
+
+use axum::http::StatusCode;
+use rust_web_server::adapters::api::openapi_json;
+use serde_json::Value;
+
+use common::TestApp;
+
+#[tokio::test]
+async fn spec_is_served_as_json() {
+    let app = TestApp::new();
+
+    let response = app.get("/openapi.json", None).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert!(response.body["openapi"].as_str().unwrap().starts_with("3."));
+    assert_eq!(
+        response.body,
+        serde_json::from_str::<Value>(&openapi_json()).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn docs_ui_is_served() {
+    let app = TestApp::new();
+
+    let response = app.get("/docs", None).await;
+    assert_eq!(response.status, StatusCode::SEE_OTHER);
+    assert_eq!(response.header("location"), Some("/docs/"));
+
+    let response = app.get("/docs/", None).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert!(response.body.as_str().unwrap().contains("<html"));
+}
+
+#[test]
+fn spec_documents_every_route() {
+    let spec: Value = serde_json::from_str(&openapi_json()).unwrap();
+
+    let mut operations = Vec::new();
+    for (path, item) in spec["paths"].as_object().unwrap() {
+        for method in item.as_object().unwrap().keys() {
+            operations.push(format!("{} {path}", method.to_uppercase()));
+        }
+    }
+    operations.sort();
+
+    assert_eq!(
+        operations,
+        vec![
+            "DELETE /todo/{id}",
+            "DELETE /user/{id}",
+            "DELETE /user/{id}/tokens/{token_id}",
+            "GET /healthz",
+            "GET /hello",
+            "GET /hello/{name}",
+            "GET /readyz",
+            "GET /status",
+            "GET /todo",
+            "GET /todo/trash",
+            "GET /todo/{id}",
+            "GET /user",
+            "GET /user/{id}",
+            "GET /user/{id}/todo",
+            "GET /user/{id}/todo/{todo_id}",
+            "GET /user/{id}/tokens",
+            "PATCH /todo/{id}",
+            "PATCH /user/{id}",
+            "POST /auth/login",
+            "POST /auth/logout",
+            "POST /todo",
+            "POST /todo/{id}/complete",
+            "POST /todo/{id}/reopen",
+            "POST /todo/{id}/restore",
+            "POST /user",
+            "POST /user/{id}/todo",
+            "POST /user/{id}/tokens",
+        ]
+    );
+}
+
+#[test]
+fn operation_ids_are_unique() {
+    let spec: Value = serde_json::from_str(&openapi_json()).unwrap();
+
+    let mut seen = HashSet::new();
+    for item in spec["paths"].as_object().unwrap().values() {
+        for operation in item.as_object().unwrap().values() {
+            let id = operation["operationId"].as_str().unwrap();
+            assert!(seen.insert(id.to_string()), "duplicate operationId {id}");
+        }
+    }
+}
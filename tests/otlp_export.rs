@@ -0,0 +1,95 @@
+mod common;
+
+use std::net::SocketAddr;
+
+use axum::{
+    body::{Body, Bytes},
+    extract::State,
+    http::{header, Request, StatusCode},
+    routing::post,
+    Router,
+};
+use rust_web_server::telemetry;
+use serde_json::json;
+use tokio::sync::mpsc;
+use tracing_subscriber::layer::SubscriberExt;
+
+use common::TestApp;
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+const PARENT_SPAN_ID: &str = "00f067aa0ba902b7";
+
+/// Stands in for an OpenTelemetry collector, forwarding the body of every
+/// OTLP/HTTP trace export it receives.
+async fn collector() -> (SocketAddr, mpsc::UnboundedReceiver<Bytes>) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let router = Router::new()
+        .route(
+            "/v1/traces",
+            post(
+                |State(sender): State<mpsc::UnboundedSender<Bytes>>, body: Bytes| async move {
+                    sender.send(body).ok();
+                },
+            ),
+        )
+        .with_state(sender);
+    let server = axum::Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0)))
+        .serve(router.into_make_service());
+    let addr = server.local_addr();
+    tokio::spawn(server);
+
+    (addr, receiver)
+}
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn spans_are_exported_in_the_callers_trace() {
+    let (collector, mut exports) = collector().await;
+    let tracer_provider = telemetry::otlp_tracer_provider(&format!("http://{collector}")).unwrap();
+    let subscriber = tracing_subscriber::registry().with(telemetry::otlp_layer(&tracer_provider));
+    let _guard = tracing::subscriber::set_default(subscriber);
+    let app = TestApp::sqlite().await;
+    let user = app.user("ada").await;
+
+    let request = Request::post("/todo")
+        .header(header::COOKIE, &user.cookie)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("traceparent", format!("00-{TRACE_ID}-{PARENT_SPAN_ID}-01"))
+        .body(Body::from(
+            json!({ "title": "milk", "description": "" }).to_string(),
+        ))
+        .unwrap();
+    let response = app.send(request).await;
+    assert_eq!(response.status, StatusCode::OK);
+
+    for result in tracer_provider.force_flush() {
+        result.unwrap();
+    }
+    let mut exported = Vec::new();
+    while let Ok(body) = exports.try_recv() {
+        exported.extend_from_slice(&body);
+    }
+
+    // Spans are protobuf encoded, ids as raw bytes and names as UTF-8
+    assert!(contains(&exported, &from_hex(TRACE_ID)));
+    assert!(contains(&exported, &from_hex(PARENT_SPAN_ID)));
+    for name in [
+        "POST /todo",
+        "TodoService.create",
+        "SqliteTodoRepository.create",
+    ] {
+        assert!(contains(&exported, name.as_bytes()), "no span named {name}");
+    }
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+use axum::http::StatusCode;
+use serde_json::Value;
+use tracing_subscriber::fmt::MakeWriter;
+
+use common::TestApp;
+
+/// Collects everything the subscriber writes.
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for Captured {
+    type Writer = Self;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+impl Captured {
+    fn events(&self) -> Vec<Value> {
+        let bytes = self.0.lock().unwrap();
+        String::from_utf8_lossy(&bytes)
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+}
+
+#[tokio::test]
+async fn requests_are_logged_in_a_span_with_route_status_and_request_id() {
+    let captured = Captured::default();
+    let subscriber = tracing_subscriber::fmt()
+        .json()
+        .with_current_span(true)
+        .with_writer(captured.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+    let app = TestApp::new();
+
+    let response = app.get("/todo/123", None).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+    app.get("/nowhere", None).await;
+
+    let finished: Vec<Value> = captured
+        .events()
+        .into_iter()
+        .filter(|event| event["fields"]["message"] == "Finished request")
+        .collect();
+    assert_eq!(finished.len(), 2);
+
+    let span = &finished[0]["span"];
+    assert_eq!(span["method"], "GET");
+    assert_eq!(span["route"], "/todo/:id");
+    assert_eq!(span["status"], 401);
+    assert!(span["latency_ms"].is_u64());
+    assert_eq!(span["request_id"].as_str(), response.header("x-request-id"));
+    assert_eq!(finished[1]["span"]["route"], "unmatched");
+    assert_eq!(finished[1]["span"]["status"], 404);
+}
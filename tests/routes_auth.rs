@@ -0,0 +1,133 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use rust_web_server::{
+    app_state::AppState,
+    domain::{clock::SteppingClock, id_generator::SequentialIdGenerator},
+};
+use serde_json::json;
+use time::{Duration, OffsetDateTime};
+
+use common::{TestApp, PASSWORD};
+
+#[tokio::test]
+async fn login_sets_http_only_session_cookie() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let response = app
+        .post(
+            "/auth/login",
+            json!({ "email": user.email, "password": PASSWORD }),
+            None,
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["id"], user.id.as_str());
+    assert!(response.body.get("password_hash").is_none());
+    let cookie = response.header("set-cookie").unwrap();
+    assert!(cookie.starts_with("session_id="));
+    assert!(cookie.contains("HttpOnly"));
+    assert!(cookie.contains("Secure"));
+}
+
+#[tokio::test]
+async fn session_cookie_may_be_sent_over_plain_http_for_development() {
+    let app = TestApp::with_state(AppState {
+        secure_cookies: false,
+        ..AppState::in_memory()
+    });
+    let user = app.user("ada").await;
+
+    let response = app
+        .post(
+            "/auth/login",
+            json!({ "email": user.email, "password": PASSWORD }),
+            None,
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert!(!response.header("set-cookie").unwrap().contains("Secure"));
+}
+
+#[tokio::test]
+async fn login_rejects_wrong_password() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let response = app
+        .post(
+            "/auth/login",
+            json!({ "email": user.email, "password": "not the password" }),
+            None,
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+    assert_eq!(response.body["code"], "unauthorized");
+    assert!(response.header("set-cookie").is_none());
+}
+
+#[tokio::test]
+async fn login_rejects_unknown_email() {
+    let app = TestApp::new();
+
+    let response = app
+        .post(
+            "/auth/login",
+            json!({ "email": "nobody@example.com", "password": PASSWORD }),
+            None,
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn logout_revokes_session() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let response = app
+        .post("/auth/logout", json!({}), Some(&user.cookie))
+        .await;
+    assert_eq!(response.status, StatusCode::NO_CONTENT);
+    assert!(response.header("set-cookie").unwrap().contains("Max-Age=0"));
+
+    let response = app.get("/todo", Some(&user.cookie)).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn protected_routes_reject_missing_or_bogus_session() {
+    let app = TestApp::new();
+
+    let response = app.get("/todo", None).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+
+    let response = app.get("/todo", Some("session_id=garbage")).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn sessions_expire_after_a_week() {
+    let clock = Arc::new(SteppingClock::new(
+        OffsetDateTime::now_utc(),
+        Duration::seconds(1),
+    ));
+    let app =
+        TestApp::with_clock_and_ids(clock.clone(), Arc::new(SequentialIdGenerator::default()));
+    let user = app.user("ada").await;
+
+    clock.advance(Duration::days(6));
+    let response = app.get("/todo", Some(&user.cookie)).await;
+    assert_eq!(response.status, StatusCode::OK);
+
+    clock.advance(Duration::days(1));
+    let response = app.get("/todo", Some(&user.cookie)).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+}
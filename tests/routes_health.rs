@@ -0,0 +1,75 @@
+mod common;
+
+use axum::http::StatusCode;
+use rust_web_server::app_state::AppState;
+use serde_json::Value;
+
+use common::TestApp;
+
+#[tokio::test]
+async fn healthz_is_ok() {
+    let app = TestApp::new();
+
+    let response = app.get("/healthz", None).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["status"], "ok");
+}
+
+#[tokio::test]
+async fn readyz_is_ready() {
+    let app = TestApp::new();
+
+    let response = app.get("/readyz", None).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["status"], "ready");
+}
+
+#[tokio::test]
+async fn readyz_fails_once_shutdown_began() {
+    let app_state = AppState::in_memory();
+    let app = TestApp::with_state(app_state.clone());
+
+    app_state.health_service.begin_shutdown();
+    let response = app.get("/readyz", None).await;
+
+    assert_eq!(response.status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.body["code"], "service_unavailable");
+    let response = app.get("/healthz", None).await;
+    assert_eq!(response.status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn status_reports_version_and_storage() {
+    let app = TestApp::new();
+
+    let response = app.get("/status", None).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["version"], env!("CARGO_PKG_VERSION"));
+    assert!(response.body["uptime_seconds"].is_u64());
+    assert_eq!(response.body["storage"]["backend"], "memory");
+    assert_eq!(response.body["storage"]["migration_version"], Value::Null);
+}
+
+#[tokio::test]
+async fn status_reports_database_pool_and_migration() {
+    let app = TestApp::sqlite().await;
+
+    let ready = app.get("/readyz", None).await;
+    let response = app.get("/status", None).await;
+
+    assert_eq!(ready.status, StatusCode::OK);
+    assert_eq!(response.status, StatusCode::OK);
+    let storage = &response.body["storage"];
+    assert_eq!(storage["backend"], "sqlite");
+    assert!(storage["pool_size"].as_u64().unwrap() >= 1);
+    assert!(storage["pool_idle"].is_u64());
+    let latest = sqlx::migrate!("./migrations/sqlite")
+        .migrations
+        .iter()
+        .map(|migration| migration.version)
+        .max();
+    assert_eq!(storage["migration_version"].as_i64(), latest);
+}
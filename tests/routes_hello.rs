@@ -0,0 +1,54 @@
+mod common;
+
+use axum::http::StatusCode;
+use serde_json::Value;
+
+use common::TestApp;
+
+#[tokio::test]
+async fn hello_defaults_to_world() {
+    let app = TestApp::new();
+
+    let response = app.get("/hello", None).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body, Value::from("Hello, World!"));
+}
+
+#[tokio::test]
+async fn hello_greets_name_from_query() {
+    let app = TestApp::new();
+
+    let response = app.get("/hello?name=Darren", None).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body, Value::from("Hello, Darren!"));
+}
+
+#[tokio::test]
+async fn hello_greets_name_from_path() {
+    let app = TestApp::new();
+
+    let response = app.get("/hello/Darren", None).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body, Value::from("Hello, Darren! From handler 2"));
+}
+
+#[tokio::test]
+async fn unknown_route_is_a_problem_not_found() {
+    let app = TestApp::new();
+
+    let response = app.get("/nope", None).await;
+
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+    assert_eq!(
+        response.header("content-type"),
+        Some("application/problem+json")
+    );
+    assert_eq!(response.body["code"], "not_found");
+    assert_eq!(
+        response.body["request_id"].as_str(),
+        response.header("x-request-id")
+    );
+}
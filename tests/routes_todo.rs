@@ -0,0 +1,913 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::{
+    async_trait,
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+};
+use rust_web_server::{
+    app_state::AppState,
+    domain::{
+        clock::{Clock, FixedClock, SystemClock},
+        entities::todo::Todo,
+        id_generator::{IdGenerator, RandomIdGenerator, SequentialIdGenerator},
+        ids::{TodoId, UserId},
+        repositories::{
+            error::{RepositoryError, RepositoryResult},
+            todo_repository::{CreateInput, ListQuery, TodoPage, TodoRepositoryPort, UpdateInput},
+        },
+    },
+    infrastructure::repositories::memory::{
+        health_repository::InMemoryHealthRepository, session_repository::InMemorySessionRepository,
+        token_repository::InMemoryTokenRepository, user_repository::InMemoryUserRepository, Store,
+    },
+};
+use serde_json::{json, Value};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+use common::{TestApp, TestResponse, TestUser};
+
+async fn create_todo(app: &TestApp, user: &TestUser, title: &str) -> Value {
+    let response = app
+        .post(
+            "/todo",
+            json!({ "title": title, "description": format!("about {title}") }),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{:?}", response.body);
+
+    response.body
+}
+
+fn titles(body: &Value) -> Vec<&str> {
+    body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|todo| todo["title"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn create_todo_is_owned_by_caller_with_defaults() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let todo = create_todo(&app, &user, "milk").await;
+
+    assert_eq!(todo["owner_id"], user.id.as_str());
+    assert_eq!(todo["title"], "milk");
+    assert_eq!(todo["priority"], "medium");
+    assert_eq!(todo["due_at"], Value::Null);
+    assert_eq!(todo["completed_at"], Value::Null);
+    assert_eq!(todo["created_at"], todo["updated_at"]);
+}
+
+#[tokio::test]
+async fn ids_and_timestamps_come_from_the_injected_generator_and_clock() {
+    let now = OffsetDateTime::parse("2030-01-01T09:00:00Z", &Rfc3339).unwrap();
+    let app = TestApp::with_clock_and_ids(
+        Arc::new(FixedClock(now)),
+        Arc::new(SequentialIdGenerator::default()),
+    );
+    let user = app.user("ada").await;
+
+    let todo = create_todo(&app, &user, "milk").await;
+
+    assert_eq!(user.id, "00000000-0000-0000-0000-000000000001");
+    assert_eq!(todo["id"], "00000000-0000-0000-0000-000000000002");
+    assert_eq!(todo["created_at"], "2030-01-01T09:00:00Z");
+
+    let response = app
+        .post(
+            "/todo/00000000-0000-0000-0000-000000000002/complete",
+            json!({}),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(response.body["completed_at"], "2030-01-01T09:00:00Z");
+}
+
+#[tokio::test]
+async fn todo_ids_sort_by_creation_time() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let mut ids = Vec::new();
+    for title in ["milk", "eggs", "flour"] {
+        let todo = create_todo(&app, &user, title).await;
+        ids.push(todo["id"].as_str().unwrap().to_owned());
+    }
+
+    assert!(ids.iter().all(|id| id.as_bytes()[14] == b'7'));
+    let mut sorted = ids.clone();
+    sorted.sort();
+    assert_eq!(ids, sorted);
+}
+
+fn timestamp(value: &Value) -> OffsetDateTime {
+    OffsetDateTime::parse(value.as_str().unwrap(), &Rfc3339).unwrap()
+}
+
+#[tokio::test]
+async fn updates_advance_updated_at_for_incremental_fetches() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    let milk = create_todo(&app, &user, "milk").await;
+    create_todo(&app, &user, "eggs").await;
+
+    let response = app
+        .patch(
+            &format!("/todo/{}", milk["id"].as_str().unwrap()),
+            json!({ "title": "oat milk" }),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(response.body["created_at"], milk["created_at"]);
+    let updated_at = timestamp(&response.body["updated_at"]);
+    assert!(updated_at > timestamp(&milk["updated_at"]));
+
+    let response = app
+        .get(
+            &format!(
+                "/todo?updated_after={}",
+                updated_at.format(&Rfc3339).unwrap().replace('+', "%2B")
+            ),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(titles(&response.body), vec!["oat milk"]);
+}
+
+#[tokio::test]
+async fn create_todo_accepts_priority_and_due_date() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let response = app
+        .post(
+            "/todo",
+            json!({
+                "title": "taxes",
+                "description": "file them",
+                "priority": "high",
+                "due_at": "2030-04-15T10:00:00Z",
+            }),
+            Some(&user.cookie),
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["priority"], "high");
+    assert_eq!(response.body["due_at"], "2030-04-15T10:00:00Z");
+}
+
+#[tokio::test]
+async fn create_todo_requires_authentication() {
+    let app = TestApp::new();
+
+    let response = app
+        .post("/todo", json!({ "title": "milk", "description": "" }), None)
+        .await;
+
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn list_todos_only_returns_callers_todos() {
+    let app = TestApp::new();
+    let ada = app.user("ada").await;
+    let grace = app.user("grace").await;
+    create_todo(&app, &ada, "ada's").await;
+    create_todo(&app, &grace, "grace's").await;
+
+    let response = app.get("/todo", Some(&ada.cookie)).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(titles(&response.body), vec!["ada's"]);
+    assert_eq!(response.body["next_cursor"], Value::Null);
+}
+
+#[tokio::test]
+async fn list_todos_paginates_with_cursor() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    for title in ["delta", "alpha", "charlie", "bravo", "echo"] {
+        create_todo(&app, &user, title).await;
+    }
+
+    let first = app
+        .get("/todo?sort=title&order=desc&limit=2", Some(&user.cookie))
+        .await;
+    assert_eq!(titles(&first.body), vec!["echo", "delta"]);
+
+    let cursor = first.body["next_cursor"].as_str().unwrap();
+    let second = app
+        .get(
+            &format!("/todo?sort=title&order=desc&limit=2&cursor={cursor}"),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(titles(&second.body), vec!["charlie", "bravo"]);
+
+    let cursor = second.body["next_cursor"].as_str().unwrap();
+    let third = app
+        .get(
+            &format!("/todo?sort=title&order=desc&limit=2&cursor={cursor}"),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(titles(&third.body), vec!["alpha"]);
+    assert_eq!(third.body["next_cursor"], Value::Null);
+}
+
+#[tokio::test]
+async fn list_todos_filters_by_search_and_completion() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    let milk = create_todo(&app, &user, "Buy milk").await;
+    create_todo(&app, &user, "Walk dog").await;
+    app.post(
+        &format!("/todo/{}/complete", milk["id"].as_str().unwrap()),
+        json!({}),
+        Some(&user.cookie),
+    )
+    .await;
+
+    let response = app.get("/todo?q=MILK", Some(&user.cookie)).await;
+    assert_eq!(titles(&response.body), vec!["Buy milk"]);
+
+    let response = app.get("/todo?completed=false", Some(&user.cookie)).await;
+    assert_eq!(titles(&response.body), vec!["Walk dog"]);
+
+    let response = app
+        .get(
+            "/todo?created_before=2000-01-01T00:00:00Z",
+            Some(&user.cookie),
+        )
+        .await;
+    assert!(titles(&response.body).is_empty());
+}
+
+#[tokio::test]
+async fn list_todos_rejects_malformed_or_mismatched_cursor() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    for title in ["a", "b"] {
+        create_todo(&app, &user, title).await;
+    }
+
+    let response = app.get("/todo?cursor=garbage", Some(&user.cookie)).await;
+    assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response.body["errors"][0]["field"], "cursor");
+
+    let page = app
+        .get("/todo?sort=title&limit=1", Some(&user.cookie))
+        .await;
+    let cursor = page.body["next_cursor"].as_str().unwrap();
+    let response = app
+        .get(
+            &format!("/todo?sort=title&order=desc&limit=1&cursor={cursor}"),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+/// Asserts a `validation_failed` problem blaming `field`.
+fn assert_validation_problem(response: &TestResponse, field: &str) {
+    assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        response.header("content-type"),
+        Some("application/problem+json")
+    );
+    assert_eq!(response.body["code"], "validation_failed");
+    assert_eq!(response.body["errors"][0]["field"], field);
+}
+
+#[tokio::test]
+async fn malformed_bodies_and_query_strings_are_problems() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let response = app
+        .send(
+            Request::post("/todo")
+                .header(header::COOKIE, &user.cookie)
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from("{\"title\": "))
+                .unwrap(),
+        )
+        .await;
+    assert_validation_problem(&response, "body");
+
+    let response = app
+        .send(
+            Request::post("/todo")
+                .header(header::COOKIE, &user.cookie)
+                .body(Body::from(r#"{"title": "milk", "description": ""}"#))
+                .unwrap(),
+        )
+        .await;
+    assert_validation_problem(&response, "body");
+    assert_eq!(
+        response.body["errors"][0]["message"],
+        "Expected request with `Content-Type: application/json`"
+    );
+
+    let response = app
+        .post("/todo", json!({ "title": 42 }), Some(&user.cookie))
+        .await;
+    assert_validation_problem(&response, "body");
+
+    let response = app.get("/todo?limit=abc", Some(&user.cookie)).await;
+    assert_validation_problem(&response, "query");
+
+    let response = app.get("/todo?sort=bogus", Some(&user.cookie)).await;
+    assert_validation_problem(&response, "query");
+}
+
+#[tokio::test]
+async fn get_todo() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    let todo = create_todo(&app, &user, "milk").await;
+
+    let response = app
+        .get(
+            &format!("/todo/{}", todo["id"].as_str().unwrap()),
+            Some(&user.cookie),
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body, todo);
+}
+
+#[tokio::test]
+async fn get_todo_is_not_modified_while_etag_matches() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    let todo = create_todo(&app, &user, "milk").await;
+    let uri = format!("/todo/{}", todo["id"].as_str().unwrap());
+
+    let response = app.get(&uri, Some(&user.cookie)).await;
+    let etag = response.header("etag").unwrap().to_string();
+
+    let response = app
+        .request_with_headers(
+            Method::GET,
+            &uri,
+            None,
+            Some(&user.cookie),
+            &[("if-none-match", &etag)],
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::NOT_MODIFIED);
+    assert_eq!(response.header("etag"), Some(etag.as_str()));
+    assert_eq!(response.body, Value::Null);
+
+    app.patch(&uri, json!({ "title": "oat milk" }), Some(&user.cookie))
+        .await;
+    let response = app
+        .request_with_headers(
+            Method::GET,
+            &uri,
+            None,
+            Some(&user.cookie),
+            &[("if-none-match", &etag)],
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_ne!(response.header("etag"), Some(etag.as_str()));
+    assert_eq!(response.body["title"], "oat milk");
+}
+
+#[tokio::test]
+async fn update_todo_with_stale_if_match_fails() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    let todo = create_todo(&app, &user, "milk").await;
+    let uri = format!("/todo/{}", todo["id"].as_str().unwrap());
+    let etag = app
+        .get(&uri, Some(&user.cookie))
+        .await
+        .header("etag")
+        .unwrap()
+        .to_string();
+
+    let response = app
+        .request_with_headers(
+            Method::PATCH,
+            &uri,
+            Some(json!({ "title": "oat milk" })),
+            Some(&user.cookie),
+            &[("if-match", &etag)],
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    let current = response.header("etag").unwrap().to_string();
+    assert_ne!(current, etag);
+
+    let response = app
+        .request_with_headers(
+            Method::PATCH,
+            &uri,
+            Some(json!({ "title": "soy milk" })),
+            Some(&user.cookie),
+            &[("if-match", &etag)],
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::PRECONDITION_FAILED);
+    assert_eq!(response.body["code"], "precondition_failed");
+
+    let response = app
+        .request_with_headers(
+            Method::PATCH,
+            &uri,
+            Some(json!({ "title": "soy milk" })),
+            Some(&user.cookie),
+            &[("if-match", &current)],
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["title"], "soy milk");
+}
+
+#[tokio::test]
+async fn state_changes_return_the_etag_for_the_next_if_match() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    let todo = create_todo(&app, &user, "milk").await;
+    let uri = format!("/todo/{}", todo["id"].as_str().unwrap());
+
+    for action in ["complete", "reopen"] {
+        let response = app
+            .post(&format!("{uri}/{action}"), json!({}), Some(&user.cookie))
+            .await;
+        assert_eq!(response.status, StatusCode::OK);
+        let current = app.get(&uri, Some(&user.cookie)).await;
+        assert_eq!(response.header("etag"), current.header("etag"), "{action}");
+    }
+
+    app.delete(&uri, Some(&user.cookie)).await;
+    let response = app
+        .post(&format!("{uri}/restore"), json!({}), Some(&user.cookie))
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    let etag = response.header("etag").unwrap().to_string();
+
+    let response = app
+        .request_with_headers(
+            Method::PATCH,
+            &uri,
+            Some(json!({ "title": "oat milk" })),
+            Some(&user.cookie),
+            &[("if-match", &etag)],
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn get_unknown_todo_is_not_found() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let response = app
+        .get(
+            "/todo/00000000-0000-0000-0000-000000000000",
+            Some(&user.cookie),
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn todo_with_malformed_id_is_bad_request() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let response = app.get("/todo/not-a-uuid", Some(&user.cookie)).await;
+
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.body["code"], "bad_request");
+    assert_eq!(
+        response.body["detail"],
+        "`not-a-uuid` is not a valid todo id"
+    );
+}
+
+#[tokio::test]
+async fn todo_titles_are_trimmed_and_bounded() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let todo = create_todo(&app, &user, "  milk ").await;
+    assert_eq!(todo["title"], "milk");
+
+    let response = app
+        .post(
+            "/todo",
+            json!({ "title": "x".repeat(201), "description": "" }),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response.body["errors"][0]["field"], "title");
+    assert_eq!(
+        response.body["errors"][0]["message"],
+        "must be at most 200 characters long"
+    );
+
+    let response = app
+        .patch(
+            &format!("/todo/{}", todo["id"].as_str().unwrap()),
+            json!({ "title": "" }),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response.body["errors"][0]["message"], "must not be empty");
+}
+
+#[tokio::test]
+async fn update_todo_changes_and_clears_fields() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    let todo = create_todo(&app, &user, "milk").await;
+    let uri = format!("/todo/{}", todo["id"].as_str().unwrap());
+
+    let response = app
+        .patch(
+            &uri,
+            json!({ "title": "oat milk", "due_at": "2030-01-01T00:00:00Z" }),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["title"], "oat milk");
+    assert_eq!(response.body["due_at"], "2030-01-01T00:00:00Z");
+
+    let response = app
+        .patch(&uri, json!({ "due_at": null }), Some(&user.cookie))
+        .await;
+    assert_eq!(response.body["title"], "oat milk");
+    assert_eq!(response.body["due_at"], Value::Null);
+}
+
+#[tokio::test]
+async fn update_todo_rejects_unknown_priority() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    let todo = create_todo(&app, &user, "milk").await;
+
+    let response = app
+        .patch(
+            &format!("/todo/{}", todo["id"].as_str().unwrap()),
+            json!({ "priority": "urgent" }),
+            Some(&user.cookie),
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn complete_and_reopen_todo() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    let todo = create_todo(&app, &user, "milk").await;
+    let id = todo["id"].as_str().unwrap();
+
+    let response = app
+        .post(
+            &format!("/todo/{id}/complete"),
+            json!({}),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    let completed_at = response.body["completed_at"].as_str().unwrap().to_string();
+
+    let response = app
+        .post(
+            &format!("/todo/{id}/complete"),
+            json!({}),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(response.body["completed_at"], completed_at.as_str());
+
+    let response = app
+        .post(&format!("/todo/{id}/reopen"), json!({}), Some(&user.cookie))
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["completed_at"], Value::Null);
+}
+
+#[tokio::test]
+async fn user_todo_routes_are_scoped_to_the_user() {
+    let app = TestApp::new();
+    let ada = app.user("ada").await;
+    let grace = app.user("grace").await;
+
+    let response = app
+        .post(
+            &format!("/user/{}/todo", ada.id),
+            json!({ "title": "milk", "description": "" }),
+            Some(&ada.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["owner_id"], ada.id.as_str());
+    let todo_id = response.body["id"].as_str().unwrap().to_string();
+
+    let response = app
+        .get(&format!("/user/{}/todo", ada.id), Some(&ada.cookie))
+        .await;
+    assert_eq!(titles(&response.body), vec!["milk"]);
+
+    let response = app
+        .get(
+            &format!("/user/{}/todo/{todo_id}", ada.id),
+            Some(&ada.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+
+    let response = app
+        .get(
+            &format!("/user/{}/todo/{todo_id}", grace.id),
+            Some(&grace.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn user_todo_routes_for_unknown_user_are_not_found() {
+    let app = TestApp::new();
+    let user = app.admin("root").await;
+
+    let response = app
+        .get(
+            "/user/00000000-0000-0000-0000-000000000000/todo",
+            Some(&user.cookie),
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn todos_of_other_users_are_forbidden() {
+    let app = TestApp::new();
+    let ada = app.user("ada").await;
+    let grace = app.user("grace").await;
+    let todo = create_todo(&app, &ada, "milk").await;
+    let id = todo["id"].as_str().unwrap();
+
+    let response = app.get(&format!("/todo/{id}"), Some(&grace.cookie)).await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+    assert_eq!(response.body["code"], "forbidden");
+
+    let response = app
+        .patch(
+            &format!("/todo/{id}"),
+            json!({ "title": "stolen" }),
+            Some(&grace.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+
+    let response = app
+        .post(
+            &format!("/todo/{id}/complete"),
+            json!({}),
+            Some(&grace.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+
+    let response = app
+        .delete(&format!("/todo/{id}"), Some(&grace.cookie))
+        .await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+
+    let response = app
+        .get(&format!("/user/{}/todo", ada.id), Some(&grace.cookie))
+        .await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+
+    let response = app.get(&format!("/todo/{id}"), Some(&ada.cookie)).await;
+    assert_eq!(response.body["title"], "milk");
+    assert_eq!(response.body["completed_at"], Value::Null);
+}
+
+#[tokio::test]
+async fn admin_can_manage_todos_of_other_users() {
+    let app = TestApp::new();
+    let ada = app.user("ada").await;
+    let root = app.admin("root").await;
+    let todo = create_todo(&app, &ada, "milk").await;
+    let id = todo["id"].as_str().unwrap();
+
+    let response = app
+        .patch(
+            &format!("/todo/{id}"),
+            json!({ "title": "oat milk" }),
+            Some(&root.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["owner_id"], ada.id.as_str());
+
+    let response = app
+        .get(&format!("/user/{}/todo", ada.id), Some(&root.cookie))
+        .await;
+    assert_eq!(titles(&response.body), vec!["oat milk"]);
+
+    let response = app.delete(&format!("/todo/{id}"), Some(&root.cookie)).await;
+    assert_eq!(response.status, StatusCode::NO_CONTENT);
+
+    let response = app
+        .post(
+            &format!("/todo/{id}/restore"),
+            json!({}),
+            Some(&root.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn delete_moves_todo_to_trash_until_restored() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    let todo = create_todo(&app, &user, "milk").await;
+    let id = todo["id"].as_str().unwrap();
+
+    let response = app.delete(&format!("/todo/{id}"), Some(&user.cookie)).await;
+    assert_eq!(response.status, StatusCode::NO_CONTENT);
+
+    let response = app.get(&format!("/todo/{id}"), Some(&user.cookie)).await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+    let response = app.delete(&format!("/todo/{id}"), Some(&user.cookie)).await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+
+    let response = app.get("/todo/trash", Some(&user.cookie)).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body[0]["id"], id);
+    assert!(response.body[0]["deleted_at"].is_string());
+
+    let response = app
+        .post(
+            &format!("/todo/{id}/restore"),
+            json!({}),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert!(response.body.get("deleted_at").is_none());
+
+    let response = app
+        .post(
+            &format!("/todo/{id}/restore"),
+            json!({}),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn members_cannot_restore_trash_of_others() {
+    let app = TestApp::new();
+    let ada = app.user("ada").await;
+    let bob = app.user("bob").await;
+    let todo = create_todo(&app, &ada, "milk").await;
+    let id = todo["id"].as_str().unwrap();
+    app.delete(&format!("/todo/{id}"), Some(&ada.cookie)).await;
+
+    let response = app
+        .post(&format!("/todo/{id}/restore"), json!({}), Some(&bob.cookie))
+        .await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+
+    let response = app.get("/todo/trash", Some(&ada.cookie)).await;
+    assert_eq!(response.body[0]["id"], id);
+}
+
+#[tokio::test]
+async fn purge_removes_only_expired_trash() {
+    let app_state = AppState::in_memory();
+    let app = TestApp::with_state(app_state.clone());
+    let user = app.user("ada").await;
+    let todo = create_todo(&app, &user, "milk").await;
+    let id = todo["id"].as_str().unwrap();
+    app.delete(&format!("/todo/{id}"), Some(&user.cookie)).await;
+
+    let purged = app_state
+        .todo_service
+        .purge_deleted(OffsetDateTime::UNIX_EPOCH)
+        .await
+        .unwrap();
+    assert_eq!(purged, 0);
+
+    let purged = app_state
+        .todo_service
+        .purge_deleted(OffsetDateTime::now_utc())
+        .await
+        .unwrap();
+    assert_eq!(purged, 1);
+
+    let response = app
+        .post(
+            &format!("/todo/{id}/restore"),
+            json!({}),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+}
+
+/// Behaves like a todo store whose database is down.
+struct UnavailableTodoRepository;
+
+#[async_trait]
+impl TodoRepositoryPort for UnavailableTodoRepository {
+    async fn list(&self, _query: ListQuery) -> RepositoryResult<TodoPage> {
+        Err(RepositoryError::Unavailable)
+    }
+
+    async fn find_by_id(&self, _id: TodoId) -> RepositoryResult<Todo> {
+        Err(RepositoryError::Unavailable)
+    }
+
+    async fn find_by_owner_and_id(&self, _owner_id: UserId, _id: TodoId) -> RepositoryResult<Todo> {
+        Err(RepositoryError::Unavailable)
+    }
+
+    async fn update_one(&self, _input: UpdateInput) -> RepositoryResult<Todo> {
+        Err(RepositoryError::Unavailable)
+    }
+
+    async fn create(&self, _input: CreateInput) -> RepositoryResult<Todo> {
+        Err(RepositoryError::Unavailable)
+    }
+
+    async fn delete(&self, _id: TodoId) -> RepositoryResult<()> {
+        Err(RepositoryError::Unavailable)
+    }
+
+    async fn list_deleted_by_owner(&self, _owner_id: UserId) -> RepositoryResult<Vec<Todo>> {
+        Err(RepositoryError::Unavailable)
+    }
+
+    async fn find_deleted_by_owner_and_id(
+        &self,
+        _owner_id: UserId,
+        _id: TodoId,
+    ) -> RepositoryResult<Todo> {
+        Err(RepositoryError::Unavailable)
+    }
+
+    async fn restore(&self, _id: TodoId) -> RepositoryResult<Todo> {
+        Err(RepositoryError::Unavailable)
+    }
+
+    async fn purge_deleted(&self, _deleted_before: OffsetDateTime) -> RepositoryResult<u64> {
+        Err(RepositoryError::Unavailable)
+    }
+}
+
+#[tokio::test]
+async fn unavailable_storage_is_service_unavailable() {
+    let store = Store::default();
+    let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+    let ids: Arc<dyn IdGenerator> = Arc::new(RandomIdGenerator);
+    let app = TestApp::with_state(AppState::from_repositories(
+        Arc::new(InMemoryHealthRepository::new(store.clone())),
+        Arc::new(InMemorySessionRepository::new(store.clone(), ids.clone())),
+        Arc::new(UnavailableTodoRepository),
+        Arc::new(InMemoryTokenRepository::new(
+            store.clone(),
+            clock.clone(),
+            ids.clone(),
+        )),
+        Arc::new(InMemoryUserRepository::new(store, clock.clone(), ids)),
+        clock,
+    ));
+    let user = app.user("ada").await;
+
+    let response = app.get("/todo", Some(&user.cookie)).await;
+
+    assert_eq!(response.status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.body["code"], "service_unavailable");
+}
@@ -0,0 +1,203 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+use common::TestApp;
+
+#[tokio::test]
+async fn token_authenticates_as_its_owner_and_records_use() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    let tokens_uri = format!("/user/{}/tokens", user.id);
+
+    let response = app
+        .post(
+            &tokens_uri,
+            json!({ "name": "CI", "scopes": ["read", "write"] }),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{:?}", response.body);
+    assert_eq!(response.body["name"], "CI");
+    assert_eq!(response.body["scopes"], json!(["read", "write"]));
+    assert!(response.body["expires_at"].is_null());
+    assert!(response.body["last_used_at"].is_null());
+    let token = response.body["token"].as_str().unwrap().to_string();
+    assert!(token.starts_with("rws_"));
+
+    let response = app
+        .request_with_token(
+            Method::POST,
+            "/todo",
+            Some(json!({ "title": "Milk", "description": "" })),
+            &token,
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{:?}", response.body);
+    assert_eq!(response.body["owner_id"], user.id.as_str());
+
+    let response = app.get(&tokens_uri, Some(&user.cookie)).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let listed = &response.body[0];
+    assert_eq!(listed["name"], "CI");
+    assert!(listed["last_used_at"].is_string());
+    assert!(listed.get("token").is_none());
+}
+
+#[tokio::test]
+async fn read_token_cannot_write() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    let token = app.token(&user, &["read"]).await;
+
+    let response = app
+        .request_with_token(Method::GET, "/todo", None, &token)
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+
+    let response = app
+        .request_with_token(
+            Method::POST,
+            "/todo",
+            Some(json!({ "title": "Milk", "description": "" })),
+            &token,
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+    assert_eq!(response.body["code"], "insufficient_scope");
+}
+
+#[tokio::test]
+async fn tokens_cannot_create_tokens() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    let token = app.token(&user, &["read", "write"]).await;
+    let tokens_uri = format!("/user/{}/tokens", user.id);
+
+    let response = app
+        .request_with_token(
+            Method::POST,
+            &tokens_uri,
+            Some(json!({ "name": "escalated", "scopes": ["read", "write"] })),
+            &token,
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+    assert_eq!(response.body["code"], "insufficient_scope");
+
+    let response = app.get(&tokens_uri, Some(&user.cookie)).await;
+    assert_eq!(response.body.as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn unknown_token_is_rejected() {
+    let app = TestApp::new();
+
+    let response = app
+        .request_with_token(Method::GET, "/todo", None, "rws_not-a-token")
+        .await;
+
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+    assert_eq!(response.header("www-authenticate"), Some("Bearer"));
+}
+
+#[tokio::test]
+async fn revoked_token_is_rejected() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    let token = app.token(&user, &["read"]).await;
+    let tokens_uri = format!("/user/{}/tokens", user.id);
+    let id = app.get(&tokens_uri, Some(&user.cookie)).await.body[0]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let response = app
+        .delete(&format!("{tokens_uri}/{id}"), Some(&user.cookie))
+        .await;
+    assert_eq!(response.status, StatusCode::NO_CONTENT);
+
+    let response = app
+        .request_with_token(Method::GET, "/todo", None, &token)
+        .await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+    let response = app.get(&tokens_uri, Some(&user.cookie)).await;
+    assert_eq!(response.body, json!([]));
+}
+
+#[tokio::test]
+async fn delete_token_with_malformed_id_is_bad_request() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let response = app
+        .delete(
+            &format!("/user/{}/tokens/not-a-uuid", user.id),
+            Some(&user.cookie),
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.body["code"], "bad_request");
+    assert_eq!(
+        response.body["detail"],
+        "`not-a-uuid` is not a valid token id"
+    );
+}
+
+#[tokio::test]
+async fn tokens_of_other_users_are_forbidden() {
+    let app = TestApp::new();
+    let ada = app.user("ada").await;
+    let grace = app.user("grace").await;
+    app.token(&ada, &["read"]).await;
+    let tokens_uri = format!("/user/{}/tokens", ada.id);
+
+    let response = app.get(&tokens_uri, Some(&grace.cookie)).await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+
+    let response = app
+        .post(
+            &tokens_uri,
+            json!({ "name": "sneaky", "scopes": ["write"] }),
+            Some(&grace.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+
+    let id = app.get(&tokens_uri, Some(&ada.cookie)).await.body[0]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let response = app
+        .delete(
+            &format!("/user/{}/tokens/{id}", grace.id),
+            Some(&grace.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn invalid_token_payload_is_rejected() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let response = app
+        .post(
+            &format!("/user/{}/tokens", user.id),
+            json!({ "name": " ", "scopes": [], "expires_at": "2020-01-01T00:00:00Z" }),
+            Some(&user.cookie),
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+    let fields: Vec<_> = response.body["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|error| error["field"].as_str().unwrap())
+        .collect();
+    assert_eq!(fields, ["name", "scopes", "expires_at"]);
+}
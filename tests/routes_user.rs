@@ -0,0 +1,365 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+use common::{TestApp, PASSWORD};
+
+#[tokio::test]
+async fn create_user_returns_user_without_credentials() {
+    let app = TestApp::new();
+
+    let response = app
+        .post(
+            "/user",
+            json!({ "email": "ada@example.com", "first_name": "Ada", "password": "long enough" }),
+            None,
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["email"], "ada@example.com");
+    assert_eq!(response.body["first_name"], "Ada");
+    assert!(response.body.get("password").is_none());
+    assert!(response.body.get("password_hash").is_none());
+}
+
+#[tokio::test]
+async fn create_user_rejects_short_password_with_field_error() {
+    let app = TestApp::new();
+
+    let response = app
+        .post(
+            "/user",
+            json!({ "email": "ada@example.com", "first_name": "Ada", "password": "short" }),
+            None,
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response.body["code"], "validation_failed");
+    assert_eq!(response.body["errors"][0]["field"], "password");
+}
+
+#[tokio::test]
+async fn create_user_rejects_malformed_email_and_blank_name_together() {
+    let app = TestApp::new();
+
+    let response = app
+        .post(
+            "/user",
+            json!({ "email": "ada.example.com", "first_name": "  ", "password": "long enough" }),
+            None,
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response.body["errors"][0]["field"], "email");
+    assert_eq!(
+        response.body["errors"][0]["message"],
+        "must be a valid email address"
+    );
+    assert_eq!(response.body["errors"][1]["field"], "first_name");
+    assert_eq!(response.body["errors"][1]["message"], "must not be empty");
+}
+
+#[tokio::test]
+async fn create_user_rejects_taken_email() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let response = app
+        .post(
+            "/user",
+            json!({ "email": user.email, "first_name": "Other", "password": "long enough" }),
+            None,
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::CONFLICT);
+    assert_eq!(response.body["code"], "email_taken");
+}
+
+#[tokio::test]
+async fn emails_are_unique_ignoring_case() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let response = app
+        .post(
+            "/user",
+            json!({ "email": "ADA@Example.com", "first_name": "Other", "password": "long enough" }),
+            None,
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::CONFLICT);
+    assert_eq!(response.body["code"], "email_taken");
+
+    let response = app
+        .post(
+            "/auth/login",
+            json!({ "email": "Ada@EXAMPLE.com", "password": PASSWORD }),
+            None,
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["id"], user.id.as_str());
+}
+
+#[tokio::test]
+async fn get_user() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let response = app
+        .get(&format!("/user/{}", user.id), Some(&user.cookie))
+        .await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["id"], user.id.as_str());
+    assert_eq!(response.body["email"], user.email.as_str());
+    assert!(response.body["created_at"].is_string());
+    assert_eq!(response.body["created_at"], response.body["updated_at"]);
+}
+
+#[tokio::test]
+async fn get_user_requires_authentication() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let response = app.get(&format!("/user/{}", user.id), None).await;
+
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn get_unknown_user_is_not_found() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let response = app
+        .get(
+            "/user/00000000-0000-0000-0000-000000000000",
+            Some(&user.cookie),
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn get_user_with_malformed_id_is_bad_request() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let response = app.get("/user/not-a-uuid", Some(&user.cookie)).await;
+
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.body["code"], "bad_request");
+    assert_eq!(
+        response.body["detail"],
+        "`not-a-uuid` is not a valid user id"
+    );
+}
+
+#[tokio::test]
+async fn update_user() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let response = app
+        .patch(
+            &format!("/user/{}", user.id),
+            json!({ "first_name": "Augusta" }),
+            Some(&user.cookie),
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["first_name"], "Augusta");
+    assert_eq!(response.body["email"], user.email.as_str());
+}
+
+#[tokio::test]
+async fn update_user_honors_if_match() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+    let uri = format!("/user/{}", user.id);
+
+    let response = app.get(&uri, Some(&user.cookie)).await;
+    let etag = response.header("etag").unwrap().to_string();
+    let response = app
+        .request_with_headers(
+            Method::GET,
+            &uri,
+            None,
+            Some(&user.cookie),
+            &[("if-none-match", &etag)],
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::NOT_MODIFIED);
+
+    app.patch(&uri, json!({ "first_name": "Augusta" }), Some(&user.cookie))
+        .await;
+    let response = app
+        .request_with_headers(
+            Method::PATCH,
+            &uri,
+            Some(json!({ "first_name": "Lovelace" })),
+            Some(&user.cookie),
+            &[("if-match", &etag)],
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::PRECONDITION_FAILED);
+
+    let response = app.get(&uri, Some(&user.cookie)).await;
+    assert_eq!(response.body["first_name"], "Augusta");
+}
+
+#[tokio::test]
+async fn update_user_rejects_email_of_another_user() {
+    let app = TestApp::new();
+    let ada = app.user("ada").await;
+    let grace = app.user("grace").await;
+
+    let response = app
+        .patch(
+            &format!("/user/{}", ada.id),
+            json!({ "email": grace.email }),
+            Some(&ada.cookie),
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::CONFLICT);
+    assert_eq!(response.body["code"], "email_taken");
+}
+
+#[tokio::test]
+async fn update_user_may_keep_their_email() {
+    let app = TestApp::new();
+    let user = app.user("ada").await;
+
+    let response = app
+        .patch(
+            &format!("/user/{}", user.id),
+            json!({ "email": user.email, "first_name": "Augusta" }),
+            Some(&user.cookie),
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::OK, "{:?}", response.body);
+    assert_eq!(response.body["email"], user.email.as_str());
+    assert_eq!(response.body["first_name"], "Augusta");
+}
+
+#[tokio::test]
+async fn members_cannot_modify_other_users() {
+    let app = TestApp::new();
+    let ada = app.user("ada").await;
+    let grace = app.user("grace").await;
+
+    let response = app
+        .patch(
+            &format!("/user/{}", ada.id),
+            json!({ "first_name": "Mallory" }),
+            Some(&grace.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+    assert_eq!(response.body["code"], "forbidden");
+
+    let response = app
+        .delete(&format!("/user/{}", ada.id), Some(&grace.cookie))
+        .await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+
+    let response = app
+        .get(&format!("/user/{}", ada.id), Some(&ada.cookie))
+        .await;
+    assert_eq!(response.body["first_name"], "ada");
+}
+
+#[tokio::test]
+async fn admin_can_modify_other_users() {
+    let app = TestApp::new();
+    let ada = app.user("ada").await;
+    let root = app.admin("root").await;
+
+    let response = app
+        .patch(
+            &format!("/user/{}", ada.id),
+            json!({ "first_name": "Augusta" }),
+            Some(&root.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["first_name"], "Augusta");
+
+    let response = app
+        .delete(&format!("/user/{}", ada.id), Some(&root.cookie))
+        .await;
+    assert_eq!(response.status, StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn list_users_is_admin_only_and_searchable() {
+    let app = TestApp::new();
+    let ada = app.user("ada").await;
+    app.user("grace").await;
+    let root = app.admin("root").await;
+
+    let response = app.get("/user", Some(&ada.cookie)).await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+
+    let response = app.get("/user", Some(&root.cookie)).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body.as_array().unwrap().len(), 3);
+
+    let response = app.get("/user?q=GRA", Some(&root.cookie)).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let users = response.body.as_array().unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0]["first_name"], "grace");
+    assert_eq!(users[0]["role"], "member");
+
+    let response = app.get("/user?q=root", Some(&root.cookie)).await;
+    assert_eq!(response.body[0]["role"], "admin");
+}
+
+#[tokio::test]
+async fn delete_user_hides_user_and_ends_sessions() {
+    let app = TestApp::new();
+    let ada = app.user("ada").await;
+    let grace = app.user("grace").await;
+
+    let response = app
+        .delete(&format!("/user/{}", ada.id), Some(&ada.cookie))
+        .await;
+    assert_eq!(response.status, StatusCode::NO_CONTENT);
+
+    let response = app
+        .get(&format!("/user/{}", ada.id), Some(&grace.cookie))
+        .await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+
+    let response = app.get("/todo", Some(&ada.cookie)).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn deleted_user_frees_their_email() {
+    let app = TestApp::new();
+    let ada = app.user("ada").await;
+
+    app.delete(&format!("/user/{}", ada.id), Some(&ada.cookie))
+        .await;
+    let response = app
+        .post(
+            "/user",
+            json!({ "email": ada.email, "first_name": "Ada", "password": "long enough" }),
+            None,
+        )
+        .await;
+
+    assert_eq!(response.status, StatusCode::OK);
+}
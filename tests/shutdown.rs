@@ -0,0 +1,121 @@
+use std::{
+    net::{SocketAddr, TcpListener},
+    time::Duration,
+};
+
+use hyper::{Client, StatusCode, Uri};
+use rust_web_server::{App, Storage};
+use tokio::{io::AsyncWriteExt, net::TcpStream, task::JoinHandle, time::timeout};
+
+/// Starts an in-memory app on a free port and waits until it answers.
+async fn start(app: App, address: SocketAddr) -> JoinHandle<()> {
+    let server = tokio::spawn(async move {
+        app.run().await.expect("server should stop cleanly");
+    });
+
+    let uri: Uri = format!("http://{address}/healthz").parse().unwrap();
+    for _ in 0..50 {
+        if let Ok(response) = Client::new().get(uri.clone()).await {
+            assert_eq!(response.status(), StatusCode::OK);
+            return server;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("server did not start");
+}
+
+fn free_address() -> SocketAddr {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn shutdown_handle_stops_the_server() {
+    let address = free_address();
+    let app = App::new(address, Storage::Memory);
+    let handle = app.shutdown_handle();
+    let server = start(app, address).await;
+
+    handle.shutdown();
+
+    timeout(Duration::from_secs(5), server)
+        .await
+        .expect("server should stop")
+        .unwrap();
+    assert!(TcpStream::connect(address).await.is_err());
+}
+
+#[tokio::test]
+async fn readiness_fails_during_the_grace_period_before_the_listener_closes() {
+    let address = free_address();
+    let app = App::new(address, Storage::Memory).with_readiness_grace(Duration::from_millis(500));
+    let handle = app.shutdown_handle();
+    let server = start(app, address).await;
+    let readyz: Uri = format!("http://{address}/readyz").parse().unwrap();
+
+    let response = Client::new().get(readyz.clone()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    handle.shutdown();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let response = Client::new().get(readyz).await.unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    timeout(Duration::from_secs(5), server)
+        .await
+        .expect("server should stop after the grace period")
+        .unwrap();
+    assert!(TcpStream::connect(address).await.is_err());
+}
+
+#[tokio::test]
+async fn shutdown_gives_up_on_stuck_requests_after_drain_timeout() {
+    let address = free_address();
+    let app = App::new(address, Storage::Memory).with_drain_timeout(Duration::from_millis(200));
+    let handle = app.shutdown_handle();
+    let server = start(app, address).await;
+
+    // A request whose headers never finish keeps its connection busy
+    let mut stuck = TcpStream::connect(address).await.unwrap();
+    stuck
+        .write_all(b"GET /healthz HTTP/1.1\r\nHost: localhost\r\n")
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    handle.shutdown();
+
+    timeout(Duration::from_secs(5), server)
+        .await
+        .expect("server should stop once the drain timeout elapsed")
+        .unwrap();
+}
+
+#[tokio::test]
+async fn metrics_can_move_to_an_admin_port() {
+    let address = free_address();
+    let metrics_address = free_address();
+    let app = App::new(address, Storage::Memory).with_metrics_address(metrics_address);
+    let handle = app.shutdown_handle();
+    let server = start(app, address).await;
+
+    let response = Client::new()
+        .get(format!("http://{metrics_address}/metrics").parse().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = Client::new()
+        .get(format!("http://{address}/metrics").parse().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    handle.shutdown();
+    timeout(Duration::from_secs(5), server)
+        .await
+        .expect("server should stop")
+        .unwrap();
+}
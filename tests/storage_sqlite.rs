@@ -0,0 +1,376 @@
+//! Runs the main flows against the SQLite repositories to make sure their
+//! queries and migrations agree with the other backends.
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::{Method, StatusCode};
+use rust_web_server::{
+    domain::{
+        clock::FixedClock,
+        id_generator::SequentialIdGenerator,
+        repositories::{
+            pagination::SortDirection,
+            todo_repository::{ListQuery, TodoRepositoryPort, TodoSortField},
+        },
+    },
+    infrastructure::repositories::sqlite::todo_repository::SqliteTodoRepository,
+};
+use serde_json::{json, Value};
+use sqlx::sqlite::SqlitePoolOptions;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use uuid::Uuid;
+
+use common::{TestApp, PASSWORD};
+
+fn titles(body: &Value) -> Vec<&str> {
+    body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|todo| todo["title"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn users_register_log_in_and_update() {
+    let app = TestApp::sqlite().await;
+    let user = app.user("ada").await;
+
+    let response = app
+        .post(
+            "/user",
+            json!({ "email": user.email.to_uppercase(), "first_name": "Other", "password": PASSWORD }),
+            None,
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::CONFLICT);
+    assert_eq!(response.body["code"], "email_taken");
+
+    let response = app
+        .patch(
+            &format!("/user/{}", user.id),
+            json!({ "email": user.email, "first_name": "Augusta" }),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["first_name"], "Augusta");
+
+    let response = app
+        .post("/auth/logout", json!({}), Some(&user.cookie))
+        .await;
+    assert_eq!(response.status, StatusCode::NO_CONTENT);
+    let response = app
+        .get(&format!("/user/{}", user.id), Some(&user.cookie))
+        .await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn todos_are_created_updated_and_completed() {
+    let app = TestApp::sqlite().await;
+    let user = app.user("ada").await;
+
+    let response = app
+        .post(
+            "/todo",
+            json!({
+                "title": "taxes",
+                "description": "file them",
+                "priority": "high",
+                "due_at": "2030-04-15T10:00:00Z",
+            }),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["owner_id"], user.id.as_str());
+    let uri = format!("/todo/{}", response.body["id"].as_str().unwrap());
+
+    let response = app
+        .patch(&uri, json!({ "due_at": null }), Some(&user.cookie))
+        .await;
+    assert_eq!(response.body["due_at"], Value::Null);
+    assert_eq!(response.body["priority"], "high");
+    let timestamp = |field: &str| {
+        OffsetDateTime::parse(response.body[field].as_str().unwrap(), &Rfc3339).unwrap()
+    };
+    assert!(timestamp("updated_at") >= timestamp("created_at"));
+
+    let response = app
+        .post(&format!("{uri}/complete"), json!({}), Some(&user.cookie))
+        .await;
+    assert!(response.body["completed_at"].is_string());
+
+    let response = app.get(&uri, Some(&user.cookie)).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert!(response.body["completed_at"].is_string());
+
+    let response = app.get("/todo/not-a-uuid", Some(&user.cookie)).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn todos_are_searched_and_paginated() {
+    let app = TestApp::sqlite().await;
+    let user = app.user("ada").await;
+    for title in ["delta", "alpha", "charlie", "bravo", "echo", "100%"] {
+        app.post(
+            "/todo",
+            json!({ "title": title, "description": "" }),
+            Some(&user.cookie),
+        )
+        .await;
+    }
+
+    let response = app.get("/todo?q=ALPHA", Some(&user.cookie)).await;
+    assert_eq!(titles(&response.body), vec!["alpha"]);
+    let response = app.get("/todo?q=%25", Some(&user.cookie)).await;
+    assert_eq!(titles(&response.body), vec!["100%"]);
+
+    let mut seen = Vec::new();
+    let mut uri = "/todo?sort=title&order=desc&limit=4".to_string();
+    loop {
+        let response = app.get(&uri, Some(&user.cookie)).await;
+        assert_eq!(response.status, StatusCode::OK);
+        seen.extend(titles(&response.body).into_iter().map(str::to_owned));
+        match response.body["next_cursor"].as_str() {
+            Some(cursor) => uri = format!("/todo?sort=title&order=desc&limit=4&cursor={cursor}"),
+            None => break,
+        }
+    }
+    assert_eq!(
+        seen,
+        vec!["echo", "delta", "charlie", "bravo", "alpha", "100%"]
+    );
+
+    let response = app.get("/todo?limit=2", Some(&user.cookie)).await;
+    let cursor = response.body["next_cursor"].as_str().unwrap();
+    let response = app
+        .get(
+            &format!("/todo?limit=10&cursor={cursor}"),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(titles(&response.body).len(), 4);
+}
+
+#[tokio::test]
+async fn rows_written_by_sqlite_paginate_at_trailing_zero_milliseconds() {
+    // Column defaults and migrations store `.120`, which sqlx's own encoding
+    // of the same instant (`.12`) sorts after.
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    sqlx::migrate!("./migrations/sqlite")
+        .run(&pool)
+        .await
+        .unwrap();
+    let owner_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO users (id, email, first_name) VALUES (?, 'ada@x.io', 'Ada')")
+        .bind(owner_id.hyphenated().to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+    for title in ["alpha", "bravo", "charlie", "delta"] {
+        sqlx::query(
+            "INSERT INTO todos (id, owner_id, title, description, created_at, updated_at) \
+             VALUES (?, ?, ?, '', '2030-01-01 09:00:05.120', '2030-01-01 09:00:05.120')",
+        )
+        .bind(Uuid::now_v7().hyphenated().to_string())
+        .bind(owner_id.hyphenated().to_string())
+        .bind(title)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+    let (clock, ids) = (
+        Arc::new(FixedClock(OffsetDateTime::UNIX_EPOCH)),
+        Arc::new(SequentialIdGenerator::default()),
+    );
+    let repository = SqliteTodoRepository::new(pool, clock, ids);
+
+    for (direction, expected) in [
+        (SortDirection::Asc, ["alpha", "bravo", "charlie", "delta"]),
+        (SortDirection::Desc, ["delta", "charlie", "bravo", "alpha"]),
+    ] {
+        let mut seen = Vec::new();
+        let mut after = None;
+        loop {
+            let page = repository
+                .list(ListQuery {
+                    owner_id: owner_id.into(),
+                    completed: None,
+                    search: None,
+                    created_after: None,
+                    created_before: None,
+                    updated_after: None,
+                    updated_before: None,
+                    sort_by: TodoSortField::CreatedAt,
+                    direction,
+                    after,
+                    limit: 1,
+                })
+                .await
+                .unwrap();
+            seen.extend(page.items.into_iter().map(|todo| todo.title));
+            assert!(seen.len() <= 4, "pages repeat rows: {seen:?}");
+            match page.next_cursor {
+                Some(cursor) => after = Some(cursor),
+                None => break,
+            }
+        }
+        assert_eq!(seen, expected);
+    }
+}
+
+#[tokio::test]
+async fn row_timestamps_come_from_the_injected_clock() {
+    let now = OffsetDateTime::parse("2030-01-01T09:00:00Z", &Rfc3339).unwrap();
+    let app = TestApp::sqlite_with_clock_and_ids(
+        Arc::new(FixedClock(now)),
+        Arc::new(SequentialIdGenerator::default()),
+    )
+    .await;
+    let user = app.user("ada").await;
+
+    let response = app
+        .get(&format!("/user/{}", user.id), Some(&user.cookie))
+        .await;
+    assert_eq!(response.body["created_at"], "2030-01-01T09:00:00Z");
+    assert_eq!(response.body["updated_at"], "2030-01-01T09:00:00Z");
+
+    let response = app
+        .post(
+            "/todo",
+            json!({ "title": "milk", "description": "" }),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(response.body["created_at"], "2030-01-01T09:00:00Z");
+    assert_eq!(response.body["updated_at"], "2030-01-01T09:00:00Z");
+    let id = response.body["id"].as_str().unwrap().to_string();
+
+    app.delete(&format!("/todo/{id}"), Some(&user.cookie)).await;
+    let response = app.get("/todo/trash", Some(&user.cookie)).await;
+    assert_eq!(response.body[0]["deleted_at"], "2030-01-01T09:00:00Z");
+    assert_eq!(response.body[0]["updated_at"], "2030-01-01T09:00:00Z");
+}
+
+#[tokio::test]
+async fn deleted_todos_are_trashed_and_restored() {
+    let app = TestApp::sqlite().await;
+    let user = app.user("ada").await;
+    let response = app
+        .post(
+            "/todo",
+            json!({ "title": "milk", "description": "" }),
+            Some(&user.cookie),
+        )
+        .await;
+    let id = response.body["id"].as_str().unwrap().to_string();
+
+    let response = app.delete(&format!("/todo/{id}"), Some(&user.cookie)).await;
+    assert_eq!(response.status, StatusCode::NO_CONTENT);
+    let response = app.get("/todo/trash", Some(&user.cookie)).await;
+    assert_eq!(response.body[0]["id"], id.as_str());
+
+    let response = app
+        .post(
+            &format!("/todo/{id}/restore"),
+            json!({}),
+            Some(&user.cookie),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    let response = app.get("/todo", Some(&user.cookie)).await;
+    assert_eq!(titles(&response.body), vec!["milk"]);
+}
+
+#[tokio::test]
+async fn tokens_are_created_used_and_revoked() {
+    let app = TestApp::sqlite().await;
+    let user = app.user("ada").await;
+    let token = app.token(&user, &["read"]).await;
+    let tokens_uri = format!("/user/{}/tokens", user.id);
+
+    let response = app
+        .request_with_token(Method::GET, &format!("/user/{}", user.id), None, &token)
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+
+    let response = app.get(&tokens_uri, Some(&user.cookie)).await;
+    assert_eq!(response.body[0]["scopes"], json!(["read"]));
+    assert!(response.body[0]["last_used_at"].is_string());
+    let id = response.body[0]["id"].as_str().unwrap().to_string();
+
+    let response = app
+        .delete(&format!("{tokens_uri}/{id}"), Some(&user.cookie))
+        .await;
+    assert_eq!(response.status, StatusCode::NO_CONTENT);
+    let response = app
+        .request_with_token(Method::GET, "/todo", None, &token)
+        .await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn admins_list_and_search_users() {
+    let app = TestApp::sqlite().await;
+    app.user("ada").await;
+    app.user("grace").await;
+    let root = app.admin("root").await;
+
+    let response = app.get("/user?q=Gra", Some(&root.cookie)).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let users = response.body.as_array().unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0]["email"], "grace@example.com");
+
+    let response = app.get("/user?q=root", Some(&root.cookie)).await;
+    assert_eq!(response.body[0]["role"], "admin");
+}
+
+#[tokio::test]
+async fn stale_updates_are_rejected() {
+    let app = TestApp::sqlite().await;
+    let user = app.user("ada").await;
+    let response = app
+        .post(
+            "/todo",
+            json!({ "title": "milk", "description": "" }),
+            Some(&user.cookie),
+        )
+        .await;
+    let uri = format!("/todo/{}", response.body["id"].as_str().unwrap());
+    let etag = app
+        .get(&uri, Some(&user.cookie))
+        .await
+        .header("etag")
+        .unwrap()
+        .to_string();
+
+    let response = app
+        .post(&format!("{uri}/complete"), json!({}), Some(&user.cookie))
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+
+    let response = app
+        .request_with_headers(
+            Method::PATCH,
+            &uri,
+            Some(json!({ "title": "oat milk" })),
+            Some(&user.cookie),
+            &[("if-match", &etag)],
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::PRECONDITION_FAILED);
+
+    let response = app.get(&uri, Some(&user.cookie)).await;
+    assert_eq!(response.body["title"], "milk");
+    assert!(response.body["completed_at"].is_string());
+}